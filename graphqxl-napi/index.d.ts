@@ -3,4 +3,4 @@
 
 /* auto-generated by NAPI-RS */
 
-export declare function graphqxlToSdl(vfs: object, entryPath: string, indentSpaces?: number | undefined | null, privatePrefix?: string | undefined | null): string
+export declare function graphqxlToSdl(vfs: object, entryPath: string, indentSpaces?: number | undefined | null, privatePrefix?: string | undefined | null, importRoots?: Array<string> | undefined | null): string
@@ -1,7 +1,10 @@
 use napi_derive::napi;
-use graphqxl_parser::{parse_spec_vfs, VirtualFileSystem};
-use graphqxl_transpiler::{transpile_spec, TranspileSpecOptions};
-use graphqxl_synthesizer::{synth_spec, SynthConfig};
+use graphqxl_config::{Config, FormatConfig};
+use graphqxl_parser::{parse_specs_vfs_with_cache, ParseCache, VirtualFileSystem};
+use graphqxl_transpiler::{transpile_spec_with_cache, TranspileCache};
+use graphqxl_synthesizer::synth_spec;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::error::Error as StdError;
 use napi::{Error, JsObject, JsString};
@@ -31,10 +34,10 @@ impl<'a> VirtualFileSystem for JsVfs<'a> {
                 std::io::ErrorKind::NotFound,
                 format!("Path not found in VFS: {}", path_str),
             ))),
-            Err(e) => Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to get from object: {}", e),
-            ))),
+            Err(e) => Err(Box::new(std::io::Error::other(format!(
+                "Failed to get from object: {}",
+                e
+            )))),
         }
     }
     
@@ -44,36 +47,85 @@ impl<'a> VirtualFileSystem for JsVfs<'a> {
     
     fn exists(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy();
-        self.obj.has_own_property(&path_str.as_ref()).unwrap_or(false)
+        self.obj.has_own_property(path_str.as_ref()).unwrap_or(false)
+    }
+
+    fn list_files(&self, dir: &Path, recursive: bool) -> std::result::Result<Vec<PathBuf>, Box<dyn StdError>> {
+        // the VFS is a flat object keyed by path, so directories are just path prefixes
+        let to_error = |e: Error| -> Box<dyn StdError> {
+            Box::new(std::io::Error::other(format!("Failed to list the VFS paths: {}", e)))
+        };
+        let keys = self.obj.get_property_names().map_err(to_error)?;
+        let length = keys.get_array_length().map_err(to_error)?;
+        let mut files = vec![];
+        for i in 0..length {
+            let key = keys.get_element::<JsString>(i).map_err(to_error)?;
+            let key = key.into_utf8().map_err(to_error)?.as_str().map_err(to_error)?.to_string();
+            let path = PathBuf::from(&key);
+            if let Ok(relative) = path.strip_prefix(dir) {
+                if recursive || relative.components().count() == 1 {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(files)
     }
 }
 
+thread_local! {
+    // kept between calls, so that only the files that changed in the VFS are parsed again
+    static PARSE_CACHE: RefCell<ParseCache> = RefCell::new(ParseCache::new());
+    // one for each entrypoint, so that only the definitions affected by the changes are transpiled again
+    static TRANSPILE_CACHES: RefCell<HashMap<String, TranspileCache>> = RefCell::new(HashMap::new());
+}
+
 #[napi]
 pub fn graphqxl_to_sdl(
     vfs: JsObject,
     entry_path: String,
     indent_spaces: Option<u32>,
     private_prefix: Option<String>,
+    import_roots: Option<Vec<String>>,
 ) -> napi::Result<String> {
     let js_vfs = JsVfs { obj: &vfs };
-    let private_prefix_val = private_prefix.unwrap_or_else(|| "_".to_string());
-    let spec = parse_spec_vfs(&entry_path, &js_vfs)
-        .map_err(|e| Error::from_reason(format!("{:?}", e)))?;
-    let transpiled = transpile_spec(
-        &spec,
-        &TranspileSpecOptions {
-            private_prefix: private_prefix_val.clone(),
-        },
-    )
-    .map_err(|e| Error::from_reason(format!("{:?}", e)))?;
-
-    let (result, _) = synth_spec(
-        transpiled,
-        SynthConfig {
-            indent_spaces: indent_spaces.unwrap_or(2) as usize,
-            private_prefix: private_prefix_val,
+    // a graphqxl.toml present in the VFS next to the entrypoint, or above it, provides the defaults
+    // its max_errors and lints are CLI-only, as the generated schema is not validated here
+    let entry_dir = Path::new(&entry_path).parent().unwrap_or(Path::new(""));
+    let mut config = Config::find(entry_dir, &js_vfs)
+        .map_err(|e| Error::from_reason(format!("{:?}", e)))?
+        .unwrap_or_default();
+    config.override_with(Config {
+        import_roots: import_roots
+            .unwrap_or_default()
+            .iter()
+            .map(PathBuf::from)
+            .collect(),
+        private_prefix,
+        format: FormatConfig {
+            indent_spaces: indent_spaces.map(|n| n as usize),
             ..Default::default()
         },
-    );
+        ..Default::default()
+    });
+    let spec = PARSE_CACHE
+        .with(|cache| {
+            parse_specs_vfs_with_cache(
+                &[&entry_path],
+                &js_vfs,
+                &config.parse_spec_options(),
+                &mut cache.borrow_mut(),
+            )
+        })
+        .map(|mut specs| specs.remove(0))
+        .map_err(|e| Error::from_reason(format!("{:?}", e)))?;
+    let transpiled = TRANSPILE_CACHES
+        .with(|caches| {
+            let mut caches = caches.borrow_mut();
+            let cache = caches.entry(entry_path.clone()).or_default();
+            transpile_spec_with_cache(&spec, &config.transpile_spec_options(), cache)
+        })
+        .map_err(|e| Error::from_reason(format!("{:?}", e)))?;
+
+    let (result, _) = synth_spec(transpiled, config.synth_config());
     Ok(result)
-}
\ No newline at end of file
+}
@@ -0,0 +1,346 @@
+use graphqxl_parser::{ParseSpecOptions, VirtualFileSystem};
+use graphqxl_synthesizer::SynthConfig;
+use graphqxl_transpiler::TranspileSpecOptions;
+use serde::Deserialize;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE_NAME: &str = "graphqxl.toml";
+pub const DEFAULT_PRIVATE_PREFIX: &str = "_";
+pub const DEFAULT_MAX_ERRORS: usize = 20;
+
+/// A file to compile, and optionally the path where the compiled schema is written.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct EntrypointConfig {
+    pub input: PathBuf,
+    pub output: Option<PathBuf>,
+}
+
+/// How the generated `.graphql` files look.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct FormatConfig {
+    pub indent_spaces: Option<usize>,
+    pub max_one_line_args: Option<usize>,
+    pub max_one_line_ors: Option<usize>,
+    pub allow_multiline_values: Option<bool>,
+}
+
+/// How the non fatal problems found in the generated schema are reported, each one
+/// of them belongs to a lint that can be allowed or denied by its name.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct LintsConfig {
+    pub allow: Vec<String>,
+    pub warn: Vec<String>,
+    pub deny: Vec<String>,
+    pub deny_warnings: Option<bool>,
+}
+
+/// What happens with the problems of a lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// They are not reported
+    Allow,
+    /// They are reported without failing
+    Warn,
+    /// They are reported as errors
+    Deny,
+}
+
+/// The settings of a project, usually read from a `graphqxl.toml` file. Anything that
+/// is not set falls back to the default value of the corresponding option.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct Config {
+    pub entrypoints: Vec<EntrypointConfig>,
+    pub import_roots: Vec<PathBuf>,
+    pub namespace_separator: Option<String>,
+    pub private_prefix: Option<String>,
+    pub inline_generic_name: Option<String>,
+    pub max_errors: Option<usize>,
+    pub format: FormatConfig,
+    pub lints: LintsConfig,
+}
+
+macro_rules! override_options {
+    ($self:ident, $other:ident, $($($field:ident).+),+) => {
+        $(
+            if $other.$($field).+.is_some() {
+                $self.$($field).+ = $other.$($field).+;
+            }
+        )+
+    };
+}
+
+impl Config {
+    /// Parses the content of a config file placed in `dir`, the paths
+    /// inside it are relative to that directory.
+    pub fn parse(content: &str, dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut config: Config = toml::from_str(content)?;
+        for entrypoint in config.entrypoints.iter_mut() {
+            entrypoint.input = dir.join(&entrypoint.input);
+            entrypoint.output = entrypoint.output.as_ref().map(|output| dir.join(output));
+        }
+        for root in config.import_roots.iter_mut() {
+            *root = dir.join(&root);
+        }
+        Ok(config)
+    }
+
+    pub fn read(path: &Path, vfs: &dyn VirtualFileSystem) -> Result<Self, Box<dyn Error>> {
+        let content = vfs.read_to_string(path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&content, dir)
+            .map_err(|err| format!("invalid config file {}: {err}", path.display()).into())
+    }
+
+    /// Looks for a `graphqxl.toml` file in `dir` and in its ancestors, the closest one wins.
+    pub fn find(dir: &Path, vfs: &dyn VirtualFileSystem) -> Result<Option<Self>, Box<dyn Error>> {
+        for ancestor in dir.ancestors() {
+            let path = ancestor.join(CONFIG_FILE_NAME);
+            if vfs.exists(&path) {
+                return Ok(Some(Self::read(&path, vfs)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replaces the settings of this config by the ones set in `other`, which usually
+    /// comes from the command line.
+    pub fn override_with(&mut self, other: Config) {
+        if !other.entrypoints.is_empty() {
+            self.entrypoints = other.entrypoints;
+        }
+        if !other.import_roots.is_empty() {
+            self.import_roots = other.import_roots;
+        }
+        for lint in other.lints.allow {
+            self.set_lint_level(lint, LintLevel::Allow);
+        }
+        for lint in other.lints.warn {
+            self.set_lint_level(lint, LintLevel::Warn);
+        }
+        for lint in other.lints.deny {
+            self.set_lint_level(lint, LintLevel::Deny);
+        }
+        override_options!(
+            self,
+            other,
+            namespace_separator,
+            private_prefix,
+            inline_generic_name,
+            max_errors,
+            format.indent_spaces,
+            format.max_one_line_args,
+            format.max_one_line_ors,
+            format.allow_multiline_values,
+            lints.deny_warnings
+        );
+    }
+
+    pub fn private_prefix(&self) -> String {
+        self.private_prefix
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PRIVATE_PREFIX.to_string())
+    }
+
+    /// Maximum number of errors reported in a single run, 0 means that there is no limit.
+    pub fn max_errors(&self) -> usize {
+        self.max_errors.unwrap_or(DEFAULT_MAX_ERRORS)
+    }
+
+    fn set_lint_level(&mut self, lint: String, level: LintLevel) {
+        let lints = &mut self.lints;
+        for list in [&mut lints.allow, &mut lints.warn, &mut lints.deny] {
+            list.retain(|other| *other != lint);
+        }
+        match level {
+            LintLevel::Allow => lints.allow.push(lint),
+            LintLevel::Warn => lints.warn.push(lint),
+            LintLevel::Deny => lints.deny.push(lint),
+        }
+    }
+
+    /// Level of a lint, `default` is used if it is not configured. With `deny_warnings`
+    /// every lint that warns is denied.
+    pub fn lint_level(&self, lint: &str, default: LintLevel) -> LintLevel {
+        let contains = |list: &Vec<String>| list.iter().any(|other| other == lint);
+        let level = if contains(&self.lints.deny) {
+            LintLevel::Deny
+        } else if contains(&self.lints.warn) {
+            LintLevel::Warn
+        } else if contains(&self.lints.allow) {
+            LintLevel::Allow
+        } else {
+            default
+        };
+        match level {
+            LintLevel::Warn if self.lints.deny_warnings.unwrap_or_default() => LintLevel::Deny,
+            level => level,
+        }
+    }
+
+    pub fn parse_spec_options(&self) -> ParseSpecOptions {
+        ParseSpecOptions {
+            import_roots: self.import_roots.clone(),
+            namespace_separator: self.namespace_separator.clone().unwrap_or_default(),
+            private_prefix: self.private_prefix(),
+        }
+    }
+
+    pub fn transpile_spec_options(&self) -> TranspileSpecOptions {
+        let default = TranspileSpecOptions::default();
+        TranspileSpecOptions {
+            private_prefix: self.private_prefix(),
+            inline_generic_name: self
+                .inline_generic_name
+                .clone()
+                .unwrap_or(default.inline_generic_name),
+        }
+    }
+
+    pub fn synth_config(&self) -> SynthConfig {
+        let default = SynthConfig::default();
+        SynthConfig {
+            indent_spaces: self.format.indent_spaces.unwrap_or(default.indent_spaces),
+            max_one_line_args: self
+                .format
+                .max_one_line_args
+                .unwrap_or(default.max_one_line_args),
+            max_one_line_ors: self
+                .format
+                .max_one_line_ors
+                .unwrap_or(default.max_one_line_ors),
+            allow_multiline_values: self
+                .format
+                .allow_multiline_values
+                .unwrap_or(default.allow_multiline_values),
+            private_prefix: self.private_prefix(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqxl_parser::MemoryFileSystem;
+
+    const CONFIG: &str = r#"
+        import_roots = ["../shared"]
+        private_prefix = "__"
+
+        [format]
+        indent_spaces = 4
+
+        [[entrypoints]]
+        input = "public.graphqxl"
+        output = "dist/public.graphql"
+    "#;
+
+    #[test]
+    fn test_paths_are_relative_to_the_config() {
+        let config = Config::parse(CONFIG, Path::new("/repo/schemas")).unwrap();
+        assert_eq!(
+            config.entrypoints,
+            vec![EntrypointConfig {
+                input: PathBuf::from("/repo/schemas/public.graphqxl"),
+                output: Some(PathBuf::from("/repo/schemas/dist/public.graphql")),
+            }]
+        );
+        assert_eq!(
+            config.parse_spec_options().import_roots,
+            vec![PathBuf::from("/repo/schemas/../shared")]
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_defaults() {
+        let config = Config::parse(CONFIG, Path::new("")).unwrap();
+        let synth_config = config.synth_config();
+        assert_eq!(synth_config.indent_spaces, 4);
+        assert_eq!(synth_config.private_prefix, "__");
+        assert_eq!(
+            synth_config.max_one_line_args,
+            SynthConfig::default().max_one_line_args
+        );
+        assert_eq!(config.max_errors(), DEFAULT_MAX_ERRORS);
+        assert_eq!(
+            config.transpile_spec_options().inline_generic_name,
+            TranspileSpecOptions::default().inline_generic_name
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut config = Config::parse(CONFIG, Path::new("")).unwrap();
+        config.override_with(Config {
+            format: FormatConfig {
+                indent_spaces: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert_eq!(config.format.indent_spaces, Some(2));
+        assert_eq!(config.private_prefix(), "__");
+        assert_eq!(config.entrypoints.len(), 1);
+    }
+
+    #[test]
+    fn test_lint_levels() {
+        let mut config = Config::parse(
+            "[lints]\nallow = [\"capitalized-value\"]\ndeny = [\"undefined-directive\"]",
+            Path::new(""),
+        )
+        .unwrap();
+        let level = |config: &Config, lint| config.lint_level(lint, LintLevel::Warn);
+        assert_eq!(level(&config, "capitalized-value"), LintLevel::Allow);
+        assert_eq!(level(&config, "undefined-directive"), LintLevel::Deny);
+        assert_eq!(level(&config, "required-argument"), LintLevel::Warn);
+        assert_eq!(
+            config.lint_level("scalar-specification-url", LintLevel::Allow),
+            LintLevel::Allow
+        );
+
+        config.override_with(Config {
+            lints: LintsConfig {
+                allow: vec!["undefined-directive".to_string()],
+                warn: vec!["scalar-specification-url".to_string()],
+                deny_warnings: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert_eq!(level(&config, "capitalized-value"), LintLevel::Allow);
+        assert_eq!(level(&config, "undefined-directive"), LintLevel::Allow);
+        assert_eq!(level(&config, "required-argument"), LintLevel::Deny);
+        assert_eq!(
+            config.lint_level("scalar-specification-url", LintLevel::Allow),
+            LintLevel::Deny
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_keys() {
+        Config::parse("indent_spaces = 4", Path::new("")).unwrap_err();
+    }
+
+    #[test]
+    fn test_finds_closest_config() {
+        let vfs = MemoryFileSystem::from([
+            ("/repo/graphqxl.toml", "private_prefix = \"repo\""),
+            (
+                "/repo/schemas/graphqxl.toml",
+                "private_prefix = \"schemas\"",
+            ),
+        ]);
+        let find = |dir: &str| Config::find(Path::new(dir), &vfs).unwrap();
+        assert_eq!(
+            find("/repo/schemas/public").unwrap().private_prefix(),
+            "schemas"
+        );
+        assert_eq!(find("/repo/other").unwrap().private_prefix(), "repo");
+        assert_eq!(find("/elsewhere"), None);
+    }
+}
@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use graphqxl_parser::{parse_specs_vfs_with_cache, MemoryFileSystem, ParseCache, ParseSpecOptions};
+
+const FILES: usize = 100;
+const TYPES_PER_FILE: usize = 10;
+const FIELDS_PER_TYPE: usize = 10;
+
+fn synthetic_file(file: usize, extra_field: bool) -> String {
+    let mut content = String::new();
+    for t in 0..TYPES_PER_FILE {
+        content += &format!("\"\"\"\nType {t} of file {file}\n\"\"\"\ntype T{file}x{t} {{\n");
+        for f in 0..FIELDS_PER_TYPE {
+            content += &format!("    field{f}(first: Int = 10, after: String): [String!]!\n");
+        }
+        if extra_field {
+            content += "    extra: Boolean\n";
+        }
+        content += "}\n\n";
+    }
+    content
+}
+
+/// A schema made of `FILES` files imported from a single entrypoint.
+fn synthetic_schema() -> MemoryFileSystem {
+    let vfs = MemoryFileSystem::new();
+    let mut main = String::new();
+    for file in 0..FILES {
+        main += &format!("import \"file{file}\"\n");
+        vfs.insert(
+            format!("/schema/file{file}.graphqxl"),
+            &synthetic_file(file, false),
+        );
+    }
+    main += "\ntype Query {\n    t: T0x0\n}\n";
+    vfs.insert("/schema/main.graphqxl", &main);
+    vfs
+}
+
+fn parse(vfs: &MemoryFileSystem, cache: &mut ParseCache) {
+    parse_specs_vfs_with_cache(
+        &["/schema/main.graphqxl"],
+        vfs,
+        &ParseSpecOptions::default(),
+        cache,
+    )
+    .unwrap();
+}
+
+fn bench_parse_cache(c: &mut Criterion) {
+    let vfs = synthetic_schema();
+    let mut group = c.benchmark_group("parse_cache");
+    group.sample_size(10);
+
+    group.bench_function("cold", |b| {
+        b.iter_batched_ref(
+            ParseCache::new,
+            |cache| parse(&vfs, cache),
+            BatchSize::LargeInput,
+        )
+    });
+
+    let mut cache = ParseCache::new();
+    parse(&vfs, &mut cache);
+    group.bench_function("unchanged", |b| b.iter(|| parse(&vfs, &mut cache)));
+
+    let mut extra_field = false;
+    group.bench_function("one_file_changed", |b| {
+        b.iter(|| {
+            extra_field = !extra_field;
+            vfs.insert("/schema/file0.graphqxl", &synthetic_file(0, extra_field));
+            parse(&vfs, &mut cache)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_cache);
+criterion_main!(benches);
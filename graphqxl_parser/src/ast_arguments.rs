@@ -1,5 +1,6 @@
 use crate::ast_description::{parse_description_and_continue, DescriptionAndNext};
 use crate::ast_identifier::{parse_identifier, Identifier};
+use crate::ast_value_basic_data::ValueBasicData;
 use crate::ast_value_data::{parse_value_data, ValueData};
 use crate::parser::{Rule, RuleError};
 use crate::utils::{unknown_rule_error, OwnedSpan};
@@ -71,6 +72,21 @@ impl Argument {
     }
 }
 
+/// Enum values are kept as identifiers, as they might be generic parameters that need
+/// to be replaced.
+fn parse_default_value(
+    pair: Pair<Rule>,
+    file: &str,
+) -> Result<ArgumentDefaultValue, Box<RuleError>> {
+    let span = OwnedSpan::from(pair.as_span(), file);
+    Ok(match parse_value_data(pair, file)? {
+        ValueData::Basic(ValueBasicData::Enum(id)) => {
+            ArgumentDefaultValue::Identifier(Identifier { id, span })
+        }
+        value_data => ArgumentDefaultValue::ValueData(value_data),
+    })
+}
+
 fn parse_argument(pair: Pair<Rule>, file: &str) -> Result<Argument, Box<RuleError>> {
     match pair.as_rule() {
         Rule::argument => {
@@ -86,9 +102,9 @@ fn parse_argument(pair: Pair<Rule>, file: &str) -> Result<Argument, Box<RuleErro
             let mut directives = Vec::new();
             if let Some(pair) = childs.next() {
                 if let Rule::value_data = pair.as_rule() {
-                    default = ArgumentDefaultValue::ValueData(parse_value_data(pair, file)?)
-                } else if let Rule::identifier = pair.as_rule() {
-                    default = ArgumentDefaultValue::Identifier(parse_identifier(pair, file)?)
+                    default = parse_default_value(pair, file)?
+                } else {
+                    directives.push(parse_directive(pair, file)?);
                 }
                 for directive in childs {
                     directives.push(parse_directive(directive, file)?);
@@ -192,6 +208,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_accept_directives_without_default_value() {
+        assert_eq!(
+            parse_input("(arg: String @dir1 @dir2)"),
+            Ok(vec![Argument::string("arg")
+                .directive(Directive::build("dir1"))
+                .directive(Directive::build("dir2"))])
+        );
+    }
+
     #[test]
     fn test_invalid_input_no_parenthesis() {
         parse_input("arg: String)").unwrap_err();
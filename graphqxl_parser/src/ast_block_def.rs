@@ -35,7 +35,7 @@ impl Display for BlockDefType {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum BlockEntry {
-    Field(BlockField),
+    Field(Box<BlockField>),
     SpreadRef(ModifiedRef),
 }
 
@@ -109,7 +109,7 @@ impl BlockDef {
     }
 
     pub fn field(&mut self, field: BlockField) -> Self {
-        self.entries.push(BlockEntry::Field(field));
+        self.entries.push(BlockEntry::Field(Box::new(field)));
         self.clone()
     }
 
@@ -179,7 +179,7 @@ fn _parse_block_def(
                         }
                         _ => {
                             let field = parse_block_field(pair.clone(), file)?;
-                            entries.push(BlockEntry::Field(field));
+                            entries.push(BlockEntry::Field(Box::new(field)));
                         }
                     }
                 }
@@ -202,13 +202,21 @@ fn _parse_block_def(
 
 pub(crate) fn parse_block_def(pair: Pair<Rule>, file: &str) -> Result<BlockDef, Box<RuleError>> {
     match pair.as_rule() {
-        Rule::type_def => _parse_block_def(pair, BlockDefType::Type, false, file),
+        Rule::type_def | Rule::graphql_type_def => {
+            _parse_block_def(pair, BlockDefType::Type, false, file)
+        }
         Rule::type_ext => _parse_block_def(pair, BlockDefType::Type, true, file),
-        Rule::input_def => _parse_block_def(pair, BlockDefType::Input, false, file),
+        Rule::input_def | Rule::graphql_input_def => {
+            _parse_block_def(pair, BlockDefType::Input, false, file)
+        }
         Rule::input_ext => _parse_block_def(pair, BlockDefType::Input, true, file),
-        Rule::enum_def => _parse_block_def(pair, BlockDefType::Enum, false, file),
+        Rule::enum_def | Rule::graphql_enum_def => {
+            _parse_block_def(pair, BlockDefType::Enum, false, file)
+        }
         Rule::enum_ext => _parse_block_def(pair, BlockDefType::Enum, true, file),
-        Rule::interface_def => _parse_block_def(pair, BlockDefType::Interface, false, file),
+        Rule::interface_def | Rule::graphql_interface_def => {
+            _parse_block_def(pair, BlockDefType::Interface, false, file)
+        }
         Rule::interface_ext => _parse_block_def(pair, BlockDefType::Interface, true, file),
         _unknown => Err(unknown_rule_error(
             pair,
@@ -455,6 +463,16 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_interface_accepts_generic_arg() {
+        assert_eq!(
+            parse_input("interface Node<T> { id: T }"),
+            Ok(BlockDef::interface_def("Node")
+                .generic(Generic::from("T"))
+                .field(BlockField::build("id").object(Identifier::from("T"))))
+        )
+    }
+
     #[test]
     fn test_interface_implements_interface() {
         assert_eq!(
@@ -1,6 +1,7 @@
 use crate::ast_arguments::{parse_arguments, Argument};
 use crate::ast_description::{parse_description_and_continue, DescriptionAndNext};
 use crate::ast_identifier::{parse_identifier, Identifier};
+use crate::ast_value_data::{parse_value_data, ValueData};
 use crate::ast_value_type::{parse_value_type, ValueType};
 use crate::parser::{Rule, RuleError};
 use crate::utils::{unknown_rule_error, OwnedSpan};
@@ -14,6 +15,7 @@ pub struct BlockField {
     pub description: String,
     pub value_type: Option<ValueType>,
     pub args: Vec<Argument>,
+    pub default: Option<ValueData>,
     pub directives: Vec<Directive>,
 }
 
@@ -65,6 +67,11 @@ impl BlockField {
         self.clone()
     }
 
+    pub fn default(&mut self, default: ValueData) -> Self {
+        self.default = Some(default);
+        self.clone()
+    }
+
     pub fn directive(&mut self, directive: Directive) -> Self {
         self.directives.push(directive);
         self.clone()
@@ -98,7 +105,11 @@ fn _parse_block_field(pair: Pair<Rule>, file: &str) -> Result<BlockField, Box<Ru
         }
     }
     for child in pairs {
-        block_field.directives.push(parse_directive(child, file)?);
+        if let Rule::value_data = child.as_rule() {
+            block_field.default = Some(parse_value_data(child, file)?);
+        } else {
+            block_field.directives.push(parse_directive(child, file)?);
+        }
     }
     Ok(block_field)
 }
@@ -118,9 +129,8 @@ pub(crate) fn parse_block_field(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast_value_data::ValueData;
     use crate::utils::parse_full_input;
-    use crate::ArgumentDefaultValue;
+    use crate::{ArgumentDefaultValue, ValueBasicData};
 
     fn parse_with_args_input(input: &str) -> Result<BlockField, Box<RuleError>> {
         parse_full_input(input, Rule::field_with_args, parse_block_field)
@@ -241,6 +251,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_without_args_accepts_default_value() {
+        assert_eq!(
+            parse_without_args_input("field: [Order!] = [ASC] @dir1"),
+            Ok(BlockField::build("field")
+                .value_type(
+                    ValueType::object(Identifier::from("Order"))
+                        .non_nullable()
+                        .array()
+                )
+                .default(ValueData::build(ValueBasicData::Enum("ASC".to_string())).list())
+                .directive(Directive::build("dir1")))
+        );
+    }
+
     #[test]
     fn test_do_not_parse_invalid() {
         parse_with_args_input("field: [String!!").unwrap_err();
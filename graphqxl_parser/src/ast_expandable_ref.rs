@@ -5,6 +5,7 @@ use crate::utils::unknown_rule_error;
 use crate::{GenericCall, Identifier, OwnedSpan, ValueType};
 use pest::iterators::Pair;
 use std::borrow::BorrowMut;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExpandableRef {
@@ -32,6 +33,21 @@ impl ExpandableRef {
     }
 }
 
+impl Display for ExpandableRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.identifier.id)?;
+        if let Some(generic_call) = &self.generic_call {
+            let args: Vec<String> = generic_call
+                .args
+                .iter()
+                .map(|arg| arg.to_string())
+                .collect();
+            write!(f, "<{}>", args.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 pub(crate) fn parse_expandable_ref(
     pair: Pair<Rule>,
     file: &str,
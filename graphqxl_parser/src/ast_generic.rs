@@ -1,13 +1,18 @@
 use pest::iterators::Pair;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 
+use crate::ast_generic_constraint::{parse_generic_constraint, GenericConstraint};
 use crate::parser::{Rule, RuleError};
-use crate::utils::unknown_rule_error;
-use crate::{parse_identifier, Identifier, OwnedSpan};
+use crate::utils::{custom_error, unknown_rule_error};
+use crate::{parse_identifier, parse_value_type, Identifier, OwnedSpan, ValueType};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Generic {
     pub span: OwnedSpan,
     pub args: Vec<Identifier>,
+    pub defaults: HashMap<String, ValueType>,
+    pub constraints: HashMap<String, GenericConstraint>,
 }
 
 impl Generic {
@@ -15,6 +20,8 @@ impl Generic {
         Generic {
             span: OwnedSpan::default(),
             args: vec![Identifier::from(name)],
+            defaults: HashMap::new(),
+            constraints: HashMap::new(),
         }
     }
 
@@ -22,6 +29,37 @@ impl Generic {
         self.args.push(Identifier::from(name));
         self.clone()
     }
+
+    pub fn arg_with_default(&mut self, name: &str, default: ValueType) -> Self {
+        self.args.push(Identifier::from(name));
+        self.defaults.insert(name.to_string(), default);
+        self.clone()
+    }
+
+    pub fn constraint(&mut self, name: &str, constraint: GenericConstraint) -> Self {
+        self.constraints.insert(name.to_string(), constraint);
+        self.clone()
+    }
+}
+
+impl Display for Generic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| {
+                let mut result = arg.id.clone();
+                if let Some(constraint) = self.constraints.get(&arg.id) {
+                    result += &format!(": {constraint}");
+                }
+                if let Some(default) = self.defaults.get(&arg.id) {
+                    result += &format!(" = {default}");
+                }
+                result
+            })
+            .collect();
+        write!(f, "<{}>", args.join(", "))
+    }
 }
 
 pub(crate) fn parse_generic(pair: Pair<Rule>, file: &str) -> Result<Generic, Box<RuleError>> {
@@ -30,11 +68,39 @@ pub(crate) fn parse_generic(pair: Pair<Rule>, file: &str) -> Result<Generic, Box
             let span = OwnedSpan::from(pair.as_span(), file);
             let childs = pair.into_inner();
             let mut args = Vec::new();
-            for name in childs {
-                args.push(parse_identifier(name, file)?);
+            let mut defaults = HashMap::new();
+            let mut constraints = HashMap::new();
+            for child in childs {
+                let mut generic_arg = child.clone().into_inner();
+                let name = parse_identifier(generic_arg.next().unwrap(), file)?;
+                let mut next = generic_arg.next();
+                if let Some(constraint) = next.clone() {
+                    if let Rule::generic_constraint = constraint.as_rule() {
+                        constraints
+                            .insert(name.id.clone(), parse_generic_constraint(constraint, file)?);
+                        next = generic_arg.next();
+                    }
+                }
+                if let Some(default) = next {
+                    defaults.insert(name.id.clone(), parse_value_type(default, file)?);
+                } else if !defaults.is_empty() {
+                    return Err(custom_error(
+                        child,
+                        &format!(
+                            "generic parameter {} needs a default value because it comes after one that has it",
+                            name.id
+                        ),
+                    ));
+                }
+                args.push(name);
             }
 
-            Ok(Generic { span, args })
+            Ok(Generic {
+                span,
+                args,
+                defaults,
+                constraints,
+            })
         }
         _unknown => Err(unknown_rule_error(pair, "generic")),
     }
@@ -43,6 +109,7 @@ pub(crate) fn parse_generic(pair: Pair<Rule>, file: &str) -> Result<Generic, Box
 #[cfg(test)]
 mod tests {
     use crate::utils::parse_full_input;
+    use crate::{GenericKind, Implements};
 
     use super::*;
 
@@ -65,6 +132,48 @@ mod tests {
         assert_eq!(parse_input("<T, C>"), Ok(Generic::from("T").arg("C")))
     }
 
+    #[test]
+    fn test_parses_generics_with_default_values() {
+        assert_eq!(
+            parse_input("<T, Cursor = String!>"),
+            Ok(Generic::from("T").arg_with_default("Cursor", ValueType::string().non_nullable()))
+        )
+    }
+
+    #[test]
+    fn test_do_not_parse_generic_without_default_after_one_with_default() {
+        parse_input("<T = Int, C>").unwrap_err();
+    }
+
+    #[test]
+    fn test_parses_generics_with_constraints() {
+        assert_eq!(
+            parse_input("<T: scalar | enum, N: implements Node = User>"),
+            Ok(Generic::from("T")
+                .constraint(
+                    "T",
+                    GenericConstraint::build()
+                        .kind(GenericKind::Scalar)
+                        .kind(GenericKind::Enum)
+                )
+                .arg_with_default("N", ValueType::object(Identifier::from("User")))
+                .constraint(
+                    "N",
+                    GenericConstraint::build().implements(Implements::from("Node"))
+                ))
+        )
+    }
+
+    #[test]
+    fn test_displays_generic_with_default_values() {
+        assert_eq!(
+            Generic::from("T")
+                .arg_with_default("Cursor", ValueType::string().non_nullable())
+                .to_string(),
+            "<T, Cursor = String!>"
+        )
+    }
+
     #[test]
     fn test_do_not_parse_incorrectly_formed_generic() {
         parse_input("T>").unwrap_err();
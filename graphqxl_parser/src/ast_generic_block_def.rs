@@ -50,6 +50,10 @@ impl GenericBlockDef {
         Self::from(BlockDefType::Input, name, block_def, Some(arg))
     }
 
+    pub fn interface_def(name: &str, block_def: &str, arg: ValueType) -> Self {
+        Self::from(BlockDefType::Interface, name, block_def, Some(arg))
+    }
+
     pub fn description(&mut self, text: &str) -> Self {
         self.description = text.to_string();
         self.clone()
@@ -117,9 +121,12 @@ pub(crate) fn parse_generic_block_def(
     match pair.as_rule() {
         Rule::generic_type_def => _parse_generic_block_def(BlockDefType::Type, pair, file),
         Rule::generic_input_def => _parse_generic_block_def(BlockDefType::Input, pair, file),
+        Rule::generic_interface_def => {
+            _parse_generic_block_def(BlockDefType::Interface, pair, file)
+        }
         _unknown => Err(unknown_rule_error(
             pair,
-            "generic_type_def, generic_input_def",
+            "generic_type_def, generic_input_def, generic_interface_def",
         )),
     }
 }
@@ -133,6 +140,8 @@ mod tests {
     fn parse_input(input: &str) -> Result<GenericBlockDef, Box<RuleError>> {
         let rule = if input.contains("input ") {
             Rule::generic_input_def
+        } else if input.contains("interface ") {
+            Rule::generic_interface_def
         } else {
             Rule::generic_type_def
         };
@@ -198,6 +207,18 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parses_generic_interface_def() {
+        assert_eq!(
+            parse_input("interface IntNode = Node<Int!>"),
+            Ok(GenericBlockDef::interface_def(
+                "IntNode",
+                "Node",
+                ValueType::int().non_nullable(),
+            ))
+        )
+    }
+
     #[test]
     fn test_parses_even_without_generic_call() {
         assert_eq!(
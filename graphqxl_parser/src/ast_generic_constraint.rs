@@ -0,0 +1,188 @@
+use crate::ast_implements::{parse_implements, Implements};
+use crate::parser::{Rule, RuleError};
+use crate::utils::{unknown_rule_error, OwnedSpan};
+use pest::iterators::Pair;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenericKind {
+    Scalar,
+    Enum,
+    Type,
+    Input,
+    Interface,
+    Union,
+}
+
+impl Display for GenericKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                GenericKind::Scalar => "scalar",
+                GenericKind::Enum => "enum",
+                GenericKind::Type => "type",
+                GenericKind::Input => "input",
+                GenericKind::Interface => "interface",
+                GenericKind::Union => "union",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenericConstraintItem {
+    Kind(GenericKind),
+    Implements(Implements),
+}
+
+impl Display for GenericConstraintItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenericConstraintItem::Kind(kind) => write!(f, "{kind}"),
+            GenericConstraintItem::Implements(implements) => {
+                let interfaces: Vec<&str> = implements
+                    .interfaces
+                    .iter()
+                    .map(|interface| interface.id.as_str())
+                    .collect();
+                write!(f, "implements {}", interfaces.join(" & "))
+            }
+        }
+    }
+}
+
+/// The argument bound to a constrained generic parameter needs
+/// to satisfy at least one of the items of the constraint.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GenericConstraint {
+    pub span: OwnedSpan,
+    pub items: Vec<GenericConstraintItem>,
+}
+
+impl GenericConstraint {
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    pub fn kind(&mut self, kind: GenericKind) -> Self {
+        self.items.push(GenericConstraintItem::Kind(kind));
+        self.clone()
+    }
+
+    pub fn implements(&mut self, implements: Implements) -> Self {
+        self.items
+            .push(GenericConstraintItem::Implements(implements));
+        self.clone()
+    }
+}
+
+impl Display for GenericConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let items: Vec<String> = self.items.iter().map(|item| item.to_string()).collect();
+        write!(f, "{}", items.join(" | "))
+    }
+}
+
+fn parse_generic_kind(pair: Pair<Rule>) -> Result<GenericKind, Box<RuleError>> {
+    match pair.as_str() {
+        "scalar" => Ok(GenericKind::Scalar),
+        "enum" => Ok(GenericKind::Enum),
+        "type" => Ok(GenericKind::Type),
+        "input" => Ok(GenericKind::Input),
+        "interface" => Ok(GenericKind::Interface),
+        "union" => Ok(GenericKind::Union),
+        _unknown => Err(unknown_rule_error(
+            pair,
+            "scalar, enum, type, input, interface or union",
+        )),
+    }
+}
+
+pub(crate) fn parse_generic_constraint(
+    pair: Pair<Rule>,
+    file: &str,
+) -> Result<GenericConstraint, Box<RuleError>> {
+    match pair.as_rule() {
+        Rule::generic_constraint => {
+            let span = OwnedSpan::from(pair.as_span(), file);
+            let mut items = Vec::new();
+            for child in pair.into_inner() {
+                match child.as_rule() {
+                    Rule::generic_kind => {
+                        items.push(GenericConstraintItem::Kind(parse_generic_kind(child)?))
+                    }
+                    _ => items.push(GenericConstraintItem::Implements(parse_implements(
+                        child, file,
+                    )?)),
+                }
+            }
+            Ok(GenericConstraint { span, items })
+        }
+        _unknown => Err(unknown_rule_error(pair, "generic_constraint")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_full_input;
+
+    fn parse_input(input: &str) -> Result<GenericConstraint, Box<RuleError>> {
+        parse_full_input(input, Rule::generic_constraint, parse_generic_constraint)
+    }
+
+    #[test]
+    fn test_parses_one_kind() {
+        assert_eq!(
+            parse_input("scalar"),
+            Ok(GenericConstraint::build().kind(GenericKind::Scalar))
+        )
+    }
+
+    #[test]
+    fn test_parses_multiple_kinds() {
+        assert_eq!(
+            parse_input("scalar | enum | interface"),
+            Ok(GenericConstraint::build()
+                .kind(GenericKind::Scalar)
+                .kind(GenericKind::Enum)
+                .kind(GenericKind::Interface))
+        )
+    }
+
+    #[test]
+    fn test_parses_implements() {
+        assert_eq!(
+            parse_input("implements Node & Entity"),
+            Ok(GenericConstraint::build().implements(Implements::from("Node").interface("Entity")))
+        )
+    }
+
+    #[test]
+    fn test_parses_kinds_and_implements() {
+        assert_eq!(
+            parse_input("input | implements Node"),
+            Ok(GenericConstraint::build()
+                .kind(GenericKind::Input)
+                .implements(Implements::from("Node")))
+        )
+    }
+
+    #[test]
+    fn test_does_not_parse_unknown_kinds() {
+        parse_input("scalars").unwrap_err();
+    }
+
+    #[test]
+    fn test_displays_constraint() {
+        assert_eq!(
+            GenericConstraint::build()
+                .kind(GenericKind::Scalar)
+                .implements(Implements::from("Node").interface("Entity"))
+                .to_string(),
+            "scalar | implements Node & Entity"
+        )
+    }
+}
@@ -0,0 +1,110 @@
+use crate::ast_description::{parse_description_and_continue, DescriptionAndNext};
+use crate::ast_expandable_ref::{parse_expandable_ref, ExpandableRef};
+use crate::ast_identifier::{parse_identifier, Identifier};
+use crate::parser::{Rule, RuleError};
+use crate::utils::{unknown_rule_error, OwnedSpan};
+use crate::{parse_directive, Directive, ValueType};
+use pest::iterators::Pair;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericUnion {
+    pub span: OwnedSpan,
+    pub name: Identifier,
+    pub description: String,
+    pub directives: Vec<Directive>,
+    pub expandable_ref: ExpandableRef,
+}
+
+impl GenericUnion {
+    pub fn build(name: &str, union: &str) -> Self {
+        Self {
+            span: OwnedSpan::default(),
+            name: Identifier::from(name),
+            description: "".to_string(),
+            directives: vec![],
+            expandable_ref: ExpandableRef::from(union),
+        }
+    }
+
+    pub fn description(&mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self.clone()
+    }
+
+    pub fn arg(&mut self, arg: ValueType) -> Self {
+        self.expandable_ref.generic_arg(arg);
+        self.clone()
+    }
+
+    pub fn directive(&mut self, directive: Directive) -> Self {
+        self.directives.push(directive);
+        self.clone()
+    }
+}
+
+pub(crate) fn parse_generic_union(
+    pair: Pair<Rule>,
+    file: &str,
+) -> Result<GenericUnion, Box<RuleError>> {
+    match pair.as_rule() {
+        Rule::generic_union_def => {
+            let span = OwnedSpan::from(pair.as_span(), file);
+            let mut childs = pair.into_inner();
+            // [description?, identifier, directives*, expandable_ref]
+            let DescriptionAndNext(description, next) =
+                parse_description_and_continue(&mut childs, file);
+            let name = parse_identifier(next.unwrap(), file)?;
+            let mut directives = vec![];
+            let mut child = childs.next().unwrap();
+            while let Rule::directive = &child.as_rule() {
+                directives.push(parse_directive(child.clone(), file)?);
+                child = childs.next().unwrap();
+            }
+            let expandable_ref = parse_expandable_ref(child, file)?;
+            Ok(GenericUnion {
+                span,
+                name,
+                description,
+                directives,
+                expandable_ref,
+            })
+        }
+        _unknown => Err(unknown_rule_error(pair, "generic_union_def")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_full_input;
+
+    fn parse_input(input: &str) -> Result<GenericUnion, Box<RuleError>> {
+        parse_full_input(input, Rule::generic_union_def, parse_generic_union)
+    }
+
+    #[test]
+    fn test_parses_generic_union() {
+        assert_eq!(
+            parse_input("union UserResult = Result<User>"),
+            Ok(GenericUnion::build("UserResult", "Result")
+                .arg(ValueType::object(Identifier::from("User"))))
+        );
+    }
+
+    #[test]
+    fn test_parses_generic_union_with_description_and_directive() {
+        assert_eq!(
+            parse_input("\"description\" union UserResult @dir = Result<User Error>"),
+            Ok(GenericUnion::build("UserResult", "Result")
+                .description("description")
+                .directive(Directive::build("dir"))
+                .arg(ValueType::object(Identifier::from("User")))
+                .arg(ValueType::object(Identifier::from("Error"))))
+        );
+    }
+
+    #[test]
+    fn test_does_not_parse_union_without_generic_call() {
+        parse_input("union UserResult = Result").unwrap_err();
+    }
+}
@@ -1,12 +1,24 @@
+use crate::ast_identifier::{parse_identifier, Identifier};
 use crate::parser::{Rule, RuleError};
 use crate::utils::unknown_rule_error;
 use crate::OwnedSpan;
 use pest::iterators::Pair;
 
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportItem {
+    pub span: OwnedSpan,
+    pub name: Identifier,
+    pub alias: Option<Identifier>,
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Import {
     pub file_name: String,
     pub span: OwnedSpan,
+    /// The definitions picked from the imported file, everything is imported if None.
+    pub items: Option<Vec<ImportItem>>,
+    /// Prefixed to the names of all the imported definitions.
+    pub namespace: Option<Identifier>,
 }
 
 impl From<&str> for Import {
@@ -14,19 +26,79 @@ impl From<&str> for Import {
         Self {
             file_name: file_name.to_string(),
             span: OwnedSpan::default(),
+            items: None,
+            namespace: None,
         }
     }
 }
 
+impl Import {
+    fn push_item(&mut self, name: &str, alias: Option<&str>) -> Self {
+        self.items.get_or_insert_with(Vec::new).push(ImportItem {
+            span: OwnedSpan::default(),
+            name: Identifier::from(name),
+            alias: alias.map(Identifier::from),
+        });
+        self.clone()
+    }
+
+    pub fn item(&mut self, name: &str) -> Self {
+        self.push_item(name, None)
+    }
+
+    pub fn item_as(&mut self, name: &str, alias: &str) -> Self {
+        self.push_item(name, Some(alias))
+    }
+
+    pub fn namespace(&mut self, namespace: &str) -> Self {
+        self.namespace = Some(Identifier::from(namespace));
+        self.clone()
+    }
+}
+
+fn parse_import_item(pair: Pair<Rule>, file: &str) -> Result<ImportItem, Box<RuleError>> {
+    match pair.as_rule() {
+        Rule::import_item => {
+            let span = OwnedSpan::from(pair.as_span(), file);
+            // [identifier, alias_keyword?, identifier?]
+            let mut childs = pair.into_inner();
+            let name = parse_identifier(childs.next().unwrap(), file)?;
+            let alias = match childs.nth(1) {
+                Some(child) => Some(parse_identifier(child, file)?),
+                None => None,
+            };
+            Ok(ImportItem { span, name, alias })
+        }
+        _ => Err(unknown_rule_error(pair, "import_item")),
+    }
+}
+
 pub(crate) fn parse_import(pair: Pair<Rule>, file: &str) -> Result<Import, Box<RuleError>> {
     match pair.as_rule() {
         Rule::import => {
             let span = OwnedSpan::from(pair.as_span(), file);
-            let rule = pair.into_inner().next().unwrap();
+            let mut childs = pair.into_inner();
+            let mut rule = childs.next().unwrap();
+            let mut items = None;
+            if let Rule::import_items = rule.as_rule() {
+                let mut parsed_items = vec![];
+                for child in rule.into_inner() {
+                    parsed_items.push(parse_import_item(child, file)?);
+                }
+                items = Some(parsed_items);
+                rule = childs.next().unwrap();
+            }
+            // [filename, alias_keyword?, identifier?]
+            let namespace = match childs.nth(1) {
+                Some(child) => Some(parse_identifier(child, file)?),
+                None => None,
+            };
 
             Ok(Import {
                 file_name: rule.as_str().trim_matches('\"').to_string(),
                 span,
+                items,
+                namespace,
             })
         }
         _ => Err(unknown_rule_error(pair, "import")),
@@ -50,6 +122,42 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parses_selective_import() {
+        assert_eq!(
+            parse_input("import { User, Product as CatalogProduct } from \"catalog\"").unwrap(),
+            Import::from("catalog")
+                .item("User")
+                .item_as("Product", "CatalogProduct")
+        )
+    }
+
+    #[test]
+    fn test_does_not_confuse_names_starting_with_as() {
+        assert_eq!(
+            parse_input("import { User asset } from \"catalog\"").unwrap(),
+            Import::from("catalog").item("User").item("asset")
+        )
+    }
+
+    #[test]
+    fn test_parses_namespaced_import() {
+        assert_eq!(
+            parse_input("import \"billing\" as Billing").unwrap(),
+            Import::from("billing").namespace("Billing")
+        )
+    }
+
+    #[test]
+    fn test_does_not_parse_namespaced_selective_import() {
+        parse_input("import { Invoice } from \"billing\" as Billing").unwrap_err();
+    }
+
+    #[test]
+    fn test_does_not_parse_empty_selective_import() {
+        parse_input("import {} from \"catalog\"").unwrap_err();
+    }
+
     #[test]
     fn test_does_not_parse_invalid_import() {
         parse_input("import my_file").unwrap_err();
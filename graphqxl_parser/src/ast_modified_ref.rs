@@ -1,14 +1,20 @@
 use crate::ast_expandable_ref::{parse_expandable_ref, ExpandableRef};
+use crate::ast_identifier::parse_identifier;
 use crate::parser::{Rule, RuleError};
 use crate::utils::unknown_rule_error;
-use crate::OwnedSpan;
+use crate::{Identifier, OwnedSpan};
 use pest::iterators::Pair;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ModifiedRef {
     Required(Box<ModifiedRef>, OwnedSpan),
     Optional(Box<ModifiedRef>, OwnedSpan),
-    ExpandableRef(ExpandableRef),
+    DeepRequired(Box<ModifiedRef>, OwnedSpan),
+    DeepOptional(Box<ModifiedRef>, OwnedSpan),
+    Pick(Box<ModifiedRef>, Vec<Identifier>, OwnedSpan),
+    Omit(Box<ModifiedRef>, Vec<Identifier>, OwnedSpan),
+    ExpandableRef(Box<ExpandableRef>),
 }
 
 impl ModifiedRef {
@@ -16,6 +22,10 @@ impl ModifiedRef {
         match self {
             ModifiedRef::Required(_, span) => span,
             ModifiedRef::Optional(_, span) => span,
+            ModifiedRef::DeepRequired(_, span) => span,
+            ModifiedRef::DeepOptional(_, span) => span,
+            ModifiedRef::Pick(_, _, span) => span,
+            ModifiedRef::Omit(_, _, span) => span,
             ModifiedRef::ExpandableRef(r) => &r.span,
         }
     }
@@ -23,11 +33,11 @@ impl ModifiedRef {
 
 impl ModifiedRef {
     pub fn build(name: &str) -> Self {
-        Self::ExpandableRef(ExpandableRef::from(name))
+        Self::ExpandableRef(Box::new(ExpandableRef::from(name)))
     }
 
     pub fn expandable_ref(expandable_ref: ExpandableRef) -> Self {
-        Self::ExpandableRef(expandable_ref)
+        Self::ExpandableRef(Box::new(expandable_ref))
     }
 
     pub fn optional(&mut self) -> Self {
@@ -37,6 +47,67 @@ impl ModifiedRef {
     pub fn required(&mut self) -> Self {
         ModifiedRef::Required(Box::new(self.clone()), OwnedSpan::default())
     }
+
+    pub fn deep_optional(&mut self) -> Self {
+        ModifiedRef::DeepOptional(Box::new(self.clone()), OwnedSpan::default())
+    }
+
+    pub fn deep_required(&mut self) -> Self {
+        ModifiedRef::DeepRequired(Box::new(self.clone()), OwnedSpan::default())
+    }
+
+    pub fn pick(&mut self, fields: &[&str]) -> Self {
+        ModifiedRef::Pick(
+            Box::new(self.clone()),
+            fields.iter().map(|field| Identifier::from(field)).collect(),
+            OwnedSpan::default(),
+        )
+    }
+
+    pub fn omit(&mut self, fields: &[&str]) -> Self {
+        ModifiedRef::Omit(
+            Box::new(self.clone()),
+            fields.iter().map(|field| Identifier::from(field)).collect(),
+            OwnedSpan::default(),
+        )
+    }
+}
+
+impl Display for ModifiedRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let selection = |fields: &[Identifier]| {
+            let fields: Vec<String> = fields.iter().map(|f| format!("\"{}\"", f.id)).collect();
+            fields.join(" | ")
+        };
+        match self {
+            ModifiedRef::Required(inner, _) => write!(f, "Required<{inner}>"),
+            ModifiedRef::Optional(inner, _) => write!(f, "Optional<{inner}>"),
+            ModifiedRef::DeepRequired(inner, _) => write!(f, "DeepRequired<{inner}>"),
+            ModifiedRef::DeepOptional(inner, _) => write!(f, "DeepOptional<{inner}>"),
+            ModifiedRef::Pick(inner, fields, _) => {
+                write!(f, "Pick<{inner}, {}>", selection(fields))
+            }
+            ModifiedRef::Omit(inner, fields, _) => {
+                write!(f, "Omit<{inner}, {}>", selection(fields))
+            }
+            ModifiedRef::ExpandableRef(expandable_ref) => write!(f, "{expandable_ref}"),
+        }
+    }
+}
+
+fn parse_field_selection(pair: Pair<Rule>, file: &str) -> Result<Vec<Identifier>, Box<RuleError>> {
+    match pair.as_rule() {
+        Rule::field_selection => {
+            let mut fields = Vec::new();
+            for selected_field in pair.into_inner() {
+                // [identifier]
+                let identifier = selected_field.into_inner().next().unwrap();
+                fields.push(parse_identifier(identifier, file)?);
+            }
+            Ok(fields)
+        }
+        _unknown => Err(unknown_rule_error(pair, "field_selection")),
+    }
 }
 
 pub(crate) fn parse_modified_ref(
@@ -63,13 +134,42 @@ pub(crate) fn parse_modified_ref(
                         span,
                     ))
                 }
-                Rule::expandable_ref => Ok(ModifiedRef::ExpandableRef(parse_expandable_ref(
-                    first, file,
-                )?)),
-                _unknown => Err(unknown_rule_error(
-                    first,
-                    "required_modifier, optional_modifier or expandable_ref",
-                )),
+                Rule::deep_required_modifier => {
+                    let second = childs.next().unwrap();
+                    Ok(ModifiedRef::DeepRequired(
+                        Box::new(parse_modified_ref(second, file)?),
+                        span,
+                    ))
+                }
+                Rule::deep_optional_modifier => {
+                    let second = childs.next().unwrap();
+                    Ok(ModifiedRef::DeepOptional(
+                        Box::new(parse_modified_ref(second, file)?),
+                        span,
+                    ))
+                }
+                Rule::pick_modifier => {
+                    let second = childs.next().unwrap();
+                    let third = childs.next().unwrap();
+                    Ok(ModifiedRef::Pick(
+                        Box::new(parse_modified_ref(second, file)?),
+                        parse_field_selection(third, file)?,
+                        span,
+                    ))
+                }
+                Rule::omit_modifier => {
+                    let second = childs.next().unwrap();
+                    let third = childs.next().unwrap();
+                    Ok(ModifiedRef::Omit(
+                        Box::new(parse_modified_ref(second, file)?),
+                        parse_field_selection(third, file)?,
+                        span,
+                    ))
+                }
+                Rule::expandable_ref => Ok(ModifiedRef::ExpandableRef(Box::new(
+                    parse_expandable_ref(first, file)?,
+                ))),
+                _unknown => Err(unknown_rule_error(first, "modifier or expandable_ref")),
             }
         }
         _unknown => Err(unknown_rule_error(pair, "modified_ref")),
@@ -117,4 +217,59 @@ mod tests {
             .required())
         )
     }
+
+    #[test]
+    fn test_parses_deep_optional_modified_ref() {
+        assert_eq!(
+            parse_input("DeepOptional<MyType>"),
+            Ok(ModifiedRef::build("MyType").deep_optional())
+        )
+    }
+
+    #[test]
+    fn test_parses_deep_required_modified_ref() {
+        assert_eq!(
+            parse_input("DeepRequired<Optional<MyType>>"),
+            Ok(ModifiedRef::build("MyType").optional().deep_required())
+        )
+    }
+
+    #[test]
+    fn test_parses_pick_modified_ref() {
+        assert_eq!(
+            parse_input("Pick<MyType, \"foo\" | \"bar\">"),
+            Ok(ModifiedRef::build("MyType").pick(&["foo", "bar"]))
+        )
+    }
+
+    #[test]
+    fn test_parses_omit_modified_ref() {
+        assert_eq!(
+            parse_input("Omit<MyType, \"foo\">"),
+            Ok(ModifiedRef::build("MyType").omit(&["foo"]))
+        )
+    }
+
+    #[test]
+    fn test_parses_nested_omit_modified_ref() {
+        assert_eq!(
+            parse_input("Optional<Omit<MyType<Int>, \"id\">>"),
+            Ok(ModifiedRef::expandable_ref(
+                ExpandableRef::from("MyType").generic_arg(ValueType::int())
+            )
+            .omit(&["id"])
+            .optional())
+        )
+    }
+
+    #[test]
+    fn test_does_not_parse_pick_without_or_operator() {
+        parse_input("Pick<MyType, \"foo\" \"bar\">").unwrap_err();
+    }
+
+    #[test]
+    fn test_displays_the_modified_ref() {
+        let input = "Pick<Optional<MyType<Int, [String!]>>, \"foo\" | \"bar\">";
+        assert_eq!(parse_input(input).unwrap().to_string(), input)
+    }
 }
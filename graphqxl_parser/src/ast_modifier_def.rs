@@ -0,0 +1,155 @@
+use crate::ast_description::{parse_description_and_continue, DescriptionAndNext};
+use crate::ast_generic::{parse_generic, Generic};
+use crate::ast_identifier::{parse_identifier, Identifier};
+use crate::ast_modified_ref::{parse_modified_ref, ModifiedRef};
+use crate::parser::{Rule, RuleError};
+use crate::utils::{custom_error, unknown_rule_error, OwnedSpan};
+use pest::iterators::Pair;
+
+const BUILT_IN_MODIFIERS: [&str; 6] = [
+    "Required",
+    "Optional",
+    "DeepRequired",
+    "DeepOptional",
+    "Pick",
+    "Omit",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModifierDef {
+    pub span: OwnedSpan,
+    pub name: Identifier,
+    pub description: String,
+    pub generic: Generic,
+    pub modified_ref: ModifiedRef,
+}
+
+impl ModifierDef {
+    pub fn build(name: &str, generic: Generic, modified_ref: ModifiedRef) -> Self {
+        Self {
+            span: OwnedSpan::default(),
+            name: Identifier::from(name),
+            description: "".to_string(),
+            generic,
+            modified_ref,
+        }
+    }
+
+    pub fn description(&mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self.clone()
+    }
+}
+
+pub(crate) fn parse_modifier_def(
+    pair: Pair<Rule>,
+    file: &str,
+) -> Result<ModifierDef, Box<RuleError>> {
+    match pair.as_rule() {
+        Rule::modifier_def => {
+            let span = OwnedSpan::from(pair.as_span(), file);
+            // [description?, modifier_keyword, identifier, generic, modified_ref]
+            let mut childs = pair.into_inner();
+            let DescriptionAndNext(description, _) =
+                parse_description_and_continue(&mut childs, file);
+            let next = childs.next().unwrap();
+            if BUILT_IN_MODIFIERS.contains(&next.as_str()) {
+                return Err(custom_error(
+                    next.clone(),
+                    &format!("{} is a built-in modifier", next.as_str()),
+                ));
+            }
+            let name = parse_identifier(next, file)?;
+            let generic = parse_generic(childs.next().unwrap(), file)?;
+            let modified_ref = parse_modified_ref(childs.next().unwrap(), file)?;
+            Ok(ModifierDef {
+                span,
+                name,
+                description,
+                generic,
+                modified_ref,
+            })
+        }
+        _unknown => Err(unknown_rule_error(pair, "modifier_def")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_full_input;
+
+    fn parse_input(input: &str) -> Result<ModifierDef, Box<RuleError>> {
+        parse_full_input(input, Rule::modifier_def, parse_modifier_def)
+    }
+
+    #[test]
+    fn test_parses_modifier_def() {
+        assert_eq!(
+            parse_input("modifier Mutable<T> = Optional<T>"),
+            Ok(ModifierDef::build(
+                "Mutable",
+                Generic::from("T"),
+                ModifiedRef::build("T").optional()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parses_modifier_def_with_description_and_nested_modifiers() {
+        assert_eq!(
+            parse_input("\"my description\" modifier Mutable<T> = Optional<Omit<T, \"id\">>"),
+            Ok(ModifierDef::build(
+                "Mutable",
+                Generic::from("T"),
+                ModifiedRef::build("T").omit(&["id"]).optional()
+            )
+            .description("my description"))
+        );
+    }
+
+    #[test]
+    fn test_parses_modifier_def_with_multiple_generic_args() {
+        assert_eq!(
+            parse_input("modifier Merge<T, U> = Required<T>"),
+            Ok(ModifierDef::build(
+                "Merge",
+                Generic::from("T").arg("U"),
+                ModifiedRef::build("T").required()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parses_modifier_def_with_any_whitespace_after_the_keyword() {
+        for input in [
+            "modifier\tMutable<T> = Optional<T>",
+            "modifier\nMutable<T> = Optional<T>",
+        ] {
+            assert_eq!(
+                parse_input(input),
+                Ok(ModifierDef::build(
+                    "Mutable",
+                    Generic::from("T"),
+                    ModifiedRef::build("T").optional()
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_does_not_parse_modifier_keyword_glued_to_the_name() {
+        parse_input("modifierMutable<T> = Optional<T>").unwrap_err();
+    }
+
+    #[test]
+    fn test_does_not_parse_modifier_def_without_generic() {
+        parse_input("modifier Mutable = Optional<User>").unwrap_err();
+    }
+
+    #[test]
+    fn test_does_not_allow_overriding_built_in_modifiers() {
+        let err = parse_input("modifier Required<T> = Optional<T>").unwrap_err();
+        assert!(err.to_string().contains("Required is a built-in modifier"));
+    }
+}
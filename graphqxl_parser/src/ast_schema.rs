@@ -61,14 +61,12 @@ enum SchemaKey {
 
 fn parse_schema_key(pair: Pair<Rule>, _file: &str) -> Result<SchemaKey, Box<RuleError>> {
     match pair.as_rule() {
-        Rule::schema_key => {
-            return match pair.as_str() {
-                "query" => Ok(SchemaKey::Query),
-                "mutation" => Ok(SchemaKey::Mutation),
-                "subscription" => Ok(SchemaKey::Subscription),
-                _ => unreachable!(),
-            }
-        }
+        Rule::schema_key => match pair.as_str() {
+            "query" => Ok(SchemaKey::Query),
+            "mutation" => Ok(SchemaKey::Mutation),
+            "subscription" => Ok(SchemaKey::Subscription),
+            _ => unreachable!(),
+        },
         _unknown => Err(unknown_rule_error(pair, "schema_key")),
     }
 }
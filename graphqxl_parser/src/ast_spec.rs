@@ -1,16 +1,22 @@
-use crate::ast_import::parse_import;
+use crate::ast_import::{parse_import, Import};
 use crate::parser::{GraphqxlParser, Rule};
-use crate::utils::{already_defined_error, custom_error, unknown_rule_error};
+use crate::utils::{unknown_rule_error, ErrorList, LocatedError};
+use crate::virtual_file_system::{
+    is_plain_graphql, is_relative_import, parse_import_glob, with_graphqxl_extension,
+    GRAPHQXL_EXTENSION,
+};
 use crate::{
-    parse_block_def, parse_directive_def, parse_generic_block_def, parse_scalar, parse_schema,
-    parse_union, BlockDef, DirectiveDef, GenericBlockDef, Identifier, OwnedSpan, Scalar, Schema,
-    Union,
+    parse_block_def, parse_directive_def, parse_generic_block_def, parse_generic_union,
+    parse_modifier_def, parse_scalar, parse_schema, parse_union, BlockDef, DirectiveDef,
+    GenericBlockDef, GenericUnion, Identifier, ModifierDef, OwnedSpan, Scalar, Schema, Union,
 };
+use crate::{NativeFileSystem, VirtualFileSystem};
 use pest::iterators::Pair;
 use pest::Parser;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -21,9 +27,12 @@ pub enum DefType {
     GenericInput(Identifier),
     Enum(Identifier),
     Interface(Identifier),
+    GenericInterface(Identifier),
     Scalar(Identifier),
     Union(Identifier),
+    GenericUnion(Identifier),
     Directive(Identifier),
+    Modifier(Identifier),
     Schema(String),
 }
 
@@ -35,9 +44,12 @@ pub struct Spec {
     pub generic_inputs: HashMap<String, GenericBlockDef>,
     pub enums: HashMap<String, BlockDef>,
     pub interfaces: HashMap<String, BlockDef>,
+    pub generic_interfaces: HashMap<String, GenericBlockDef>,
     pub scalars: HashMap<String, Scalar>,
     pub unions: HashMap<String, Union>,
+    pub generic_unions: HashMap<String, GenericUnion>,
     pub directives: HashMap<String, DirectiveDef>,
+    pub modifiers: HashMap<String, ModifierDef>,
     pub schemas: HashMap<String, Schema>,
     pub order: Vec<DefType>,
 }
@@ -53,118 +65,268 @@ impl Spec {
         clone
     }
 
+    /// Identifier of the definition named `id`, extensions are not taken into account.
+    pub fn definition(&self, id: &str) -> Option<&Identifier> {
+        self.order.iter().find_map(|def| match def {
+            DefType::Type(name)
+            | DefType::GenericType(name)
+            | DefType::Input(name)
+            | DefType::GenericInput(name)
+            | DefType::Enum(name)
+            | DefType::Interface(name)
+            | DefType::GenericInterface(name)
+            | DefType::Scalar(name)
+            | DefType::Union(name)
+            | DefType::GenericUnion(name)
+            | DefType::Directive(name)
+            | DefType::Modifier(name) => (name.id == id).then_some(name),
+            DefType::Schema(_) => None,
+        })
+    }
+
+    /// Error for a definition named like a previous one, which is pointed to as well.
+    fn already_defined_error(&self, name: &Identifier, kind: &str) -> Box<LocatedError> {
+        let err = name
+            .span
+            .make_error(&format!("{kind} \"{}\" is already defined", name.id));
+        match self.definition(&name.id) {
+            Some(first) => err.with_label(first.span.make_label("first defined here")),
+            None => err,
+        }
+    }
+
+    /// A definition can be reached through several imports when some of them are selective,
+    /// in that case it is the same definition if it comes from the same place of the same file.
+    fn is_already_merged(&self, def: &DefType, other: &Spec) -> bool {
+        fn same_source(a: &OwnedSpan, b: &OwnedSpan) -> bool {
+            a.file == b.file && a.start == b.start
+        }
+        macro_rules! contains {
+            ($map:ident, $key:expr) => {
+                match (self.$map.get($key), other.$map.get($key)) {
+                    (Some(mine), Some(theirs)) => same_source(&mine.span, &theirs.span),
+                    // extensions are stored with unique keys, so they need to be searched
+                    (None, Some(theirs)) => self
+                        .$map
+                        .values()
+                        .any(|mine| same_source(&mine.span, &theirs.span)),
+                    (_, None) => false,
+                }
+            };
+        }
+        match def {
+            DefType::Type(key) => contains!(types, &key.id),
+            DefType::GenericType(key) => contains!(generic_types, &key.id),
+            DefType::Input(key) => contains!(inputs, &key.id),
+            DefType::GenericInput(key) => contains!(generic_inputs, &key.id),
+            DefType::Enum(key) => contains!(enums, &key.id),
+            DefType::Interface(key) => contains!(interfaces, &key.id),
+            DefType::GenericInterface(key) => contains!(generic_interfaces, &key.id),
+            DefType::Scalar(key) => contains!(scalars, &key.id),
+            DefType::Union(key) => contains!(unions, &key.id),
+            DefType::GenericUnion(key) => contains!(generic_unions, &key.id),
+            DefType::Directive(key) => contains!(directives, &key.id),
+            DefType::Modifier(key) => contains!(modifiers, &key.id),
+            DefType::Schema(key) => contains!(schemas, key),
+        }
+    }
+
+    /// Merges the spec of an imported file, keeping only the definitions picked by the
+    /// import, under their aliases, if it is a selective one.
+    fn merge_import(
+        &mut self,
+        import: &Import,
+        mut other: Spec,
+        options: &ParseSpecOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if let Some(items) = &import.items {
+            let mut renames = HashMap::new();
+            for item in items.iter() {
+                if !other.defines(&item.name.id) {
+                    return Err(item.span.make_error(&format!(
+                        "{} is not defined in \"{}\"",
+                        item.name.id, import.file_name
+                    )));
+                }
+                if let Some(alias) = &item.alias {
+                    renames.insert(item.name.id.clone(), alias.id.clone());
+                }
+            }
+            // an alias cannot take the name of a definition that keeps its own
+            for alias in items.iter().filter_map(|item| item.alias.as_ref()) {
+                if other.defines(&alias.id) && !renames.contains_key(&alias.id) {
+                    return Err(alias.span.make_error(&format!(
+                        "{} is already defined in \"{}\"",
+                        alias.id, import.file_name
+                    )));
+                }
+            }
+            other.rename(&renames);
+            let names: Vec<&str> = items
+                .iter()
+                .map(|item| item.alias.as_ref().unwrap_or(&item.name).id.as_str())
+                .collect();
+            other.select(&names);
+        }
+        if let Some(namespace) = &import.namespace {
+            let prefix = namespace.id.clone() + &options.namespace_separator;
+            other.prefix(&prefix, &options.private_prefix);
+        }
+        self.merge(other)
+    }
+
+    /// Merges another spec into this one.
     fn merge(&mut self, other: Spec) -> Result<(), Box<dyn Error>> {
-        for el in other.order.into_iter() {
-            match &el {
-                DefType::Type(name) => {
-                    if self.types.contains_key(&name.id)
-                        || self.generic_types.contains_key(&name.id)
-                    {
-                        return Err(name.span.make_error("Duplicated type"));
-                    }
-                    self.types.insert(
-                        name.id.to_string(),
-                        other.types.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::GenericType(name) => {
-                    if self.generic_types.contains_key(&name.id)
-                        || self.types.contains_key(&name.id)
-                    {
-                        return Err(name.span.make_error("Duplicated type"));
-                    }
-                    self.generic_types.insert(
-                        name.id.to_string(),
-                        other.generic_types.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::Input(name) => {
-                    if self.inputs.contains_key(&name.id)
-                        && self.generic_inputs.contains_key(&name.id)
-                    {
-                        return Err(name.span.make_error("Duplicated input"));
-                    }
-                    self.inputs.insert(
-                        name.id.to_string(),
-                        other.inputs.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::GenericInput(name) => {
-                    if self.generic_inputs.contains_key(&name.id)
-                        || self.inputs.contains_key(&name.id)
-                    {
-                        return Err(name.span.make_error("Duplicated input"));
-                    }
-                    self.generic_inputs.insert(
-                        name.id.to_string(),
-                        other.generic_inputs.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::Enum(name) => {
-                    if self.enums.contains_key(&name.id) {
-                        return Err(name.span.make_error("Duplicated enum"));
-                    }
-                    self.enums.insert(
-                        name.id.to_string(),
-                        other.enums.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::Interface(name) => {
-                    if self.interfaces.contains_key(&name.id) {
-                        return Err(name.span.make_error("Duplicated interface"));
-                    }
-                    self.interfaces.insert(
-                        name.id.to_string(),
-                        other.interfaces.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::Scalar(name) => {
-                    if self.scalars.contains_key(&name.id) {
-                        return Err(name.span.make_error("Duplicated scalar"));
-                    }
-                    self.scalars.insert(
-                        name.id.to_string(),
-                        other.scalars.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::Union(name) => {
-                    if self.unions.contains_key(&name.id) {
-                        return Err(name.span.make_error("Duplicated union"));
-                    }
-                    self.unions.insert(
-                        name.id.to_string(),
-                        other.unions.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::Directive(name) => {
-                    if self.directives.contains_key(&name.id) {
-                        return Err(name.span.make_error("Duplicated directive"));
-                    }
-                    self.directives.insert(
-                        name.id.to_string(),
-                        other.directives.get(&name.id).unwrap().clone(),
-                    );
-                }
-                DefType::Schema(name) => {
-                    if let Some(schema) = self.schemas.get(name) {
-                        return Err(schema.span.make_error("Schema defined multiple times"));
-                    }
-                    self.schemas
-                        .insert(name.clone(), other.schemas.get(name).unwrap().clone());
+        let mut errors = vec![];
+        for el in other.order.clone().into_iter() {
+            if self.is_already_merged(&el, &other) {
+                continue;
+            }
+            if let Err(err) = self.merge_def(el, &other) {
+                errors.push(err);
+            }
+        }
+        ErrorList::check(errors)
+    }
+
+    fn merge_def(&mut self, el: DefType, other: &Spec) -> Result<(), Box<dyn Error>> {
+        match &el {
+            DefType::Type(name) => {
+                if self.types.contains_key(&name.id) || self.generic_types.contains_key(&name.id) {
+                    return Err(self.already_defined_error(name, "type"));
+                }
+                self.types.insert(
+                    name.id.to_string(),
+                    other.types.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::GenericType(name) => {
+                if self.generic_types.contains_key(&name.id) || self.types.contains_key(&name.id) {
+                    return Err(self.already_defined_error(name, "type"));
+                }
+                self.generic_types.insert(
+                    name.id.to_string(),
+                    other.generic_types.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Input(name) => {
+                if self.inputs.contains_key(&name.id) || self.generic_inputs.contains_key(&name.id)
+                {
+                    return Err(self.already_defined_error(name, "input"));
+                }
+                self.inputs.insert(
+                    name.id.to_string(),
+                    other.inputs.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::GenericInput(name) => {
+                if self.generic_inputs.contains_key(&name.id) || self.inputs.contains_key(&name.id)
+                {
+                    return Err(self.already_defined_error(name, "input"));
+                }
+                self.generic_inputs.insert(
+                    name.id.to_string(),
+                    other.generic_inputs.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Enum(name) => {
+                if self.enums.contains_key(&name.id) {
+                    return Err(self.already_defined_error(name, "enum"));
+                }
+                self.enums.insert(
+                    name.id.to_string(),
+                    other.enums.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Interface(name) => {
+                if self.interfaces.contains_key(&name.id)
+                    || self.generic_interfaces.contains_key(&name.id)
+                {
+                    return Err(self.already_defined_error(name, "interface"));
+                }
+                self.interfaces.insert(
+                    name.id.to_string(),
+                    other.interfaces.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::GenericInterface(name) => {
+                if self.generic_interfaces.contains_key(&name.id)
+                    || self.interfaces.contains_key(&name.id)
+                {
+                    return Err(self.already_defined_error(name, "interface"));
+                }
+                self.generic_interfaces.insert(
+                    name.id.to_string(),
+                    other.generic_interfaces.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Scalar(name) => {
+                if self.scalars.contains_key(&name.id) {
+                    return Err(self.already_defined_error(name, "scalar"));
+                }
+                self.scalars.insert(
+                    name.id.to_string(),
+                    other.scalars.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Union(name) => {
+                if self.unions.contains_key(&name.id) || self.generic_unions.contains_key(&name.id)
+                {
+                    return Err(self.already_defined_error(name, "union"));
                 }
+                self.unions.insert(
+                    name.id.to_string(),
+                    other.unions.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::GenericUnion(name) => {
+                if self.generic_unions.contains_key(&name.id) || self.unions.contains_key(&name.id)
+                {
+                    return Err(self.already_defined_error(name, "union"));
+                }
+                self.generic_unions.insert(
+                    name.id.to_string(),
+                    other.generic_unions.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Directive(name) => {
+                if self.directives.contains_key(&name.id) {
+                    return Err(self.already_defined_error(name, "directive"));
+                }
+                self.directives.insert(
+                    name.id.to_string(),
+                    other.directives.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Modifier(name) => {
+                if self.modifiers.contains_key(&name.id) {
+                    return Err(self.already_defined_error(name, "modifier"));
+                }
+                self.modifiers.insert(
+                    name.id.to_string(),
+                    other.modifiers.get(&name.id).unwrap().clone(),
+                );
+            }
+            DefType::Schema(name) => {
+                if let Some(first) = self.schemas.get(name) {
+                    let schema = other.schemas.get(name).unwrap();
+                    return Err(schema_already_defined_error(&schema.span, &first.span));
+                }
+                self.schemas
+                    .insert(name.clone(), other.schemas.get(name).unwrap().clone());
             }
-            self.order.push(el);
         }
+        self.order.push(el);
         Ok(())
     }
 
-    fn add(&mut self, pair: Pair<Rule>, file: &str) -> Result<(), Box<dyn Error>> {
+    pub(crate) fn add(&mut self, pair: Pair<Rule>, file: &str) -> Result<(), Box<dyn Error>> {
         match pair.as_rule() {
             Rule::schema_def => {
                 let schema = parse_schema(pair.clone(), file)?;
                 let id = "schema".to_string();
-                if self.schemas.contains_key(&id) {
-                    Err(Box::new(custom_error(pair, "schema is already defined")))
+                if let Some(first) = self.schemas.get(&id) {
+                    Err(schema_already_defined_error(&schema.span, &first.span))
                 } else {
                     self.schemas.insert(id.clone(), schema);
                     self.order.push(DefType::Schema(id));
@@ -178,11 +340,11 @@ impl Spec {
                 self.order.push(DefType::Schema(id));
                 Ok(())
             }
-            Rule::type_def => {
+            Rule::type_def | Rule::graphql_type_def => {
                 let block_def = parse_block_def(pair.clone(), file)?;
                 let id = block_def.name.clone();
                 if self.types.contains_key(&id.id) || self.generic_types.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "type", &id.id)))
+                    Err(self.already_defined_error(&id, "type"))
                 } else {
                     self.types.insert(id.id.clone(), block_def);
                     self.order.push(DefType::Type(id));
@@ -200,18 +362,18 @@ impl Spec {
                 let generic_block_def = parse_generic_block_def(pair.clone(), file)?;
                 let id = generic_block_def.name.clone();
                 if self.generic_types.contains_key(&id.id) || self.types.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "type", &id.id)))
+                    Err(self.already_defined_error(&id, "type"))
                 } else {
                     self.generic_types.insert(id.id.clone(), generic_block_def);
                     self.order.push(DefType::GenericType(id));
                     Ok(())
                 }
             }
-            Rule::input_def => {
+            Rule::input_def | Rule::graphql_input_def => {
                 let block_def = parse_block_def(pair.clone(), file)?;
                 let id = block_def.name.clone();
                 if self.inputs.contains_key(&id.id) || self.generic_inputs.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "input", &id.id)))
+                    Err(self.already_defined_error(&id, "input"))
                 } else {
                     self.inputs.insert(id.id.clone(), block_def);
                     self.order.push(DefType::Input(id));
@@ -229,18 +391,18 @@ impl Spec {
                 let generic_block_def = parse_generic_block_def(pair.clone(), file)?;
                 let id = generic_block_def.name.clone();
                 if self.generic_inputs.contains_key(&id.id) || self.inputs.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "input", &id.id)))
+                    Err(self.already_defined_error(&id, "input"))
                 } else {
                     self.generic_inputs.insert(id.id.clone(), generic_block_def);
                     self.order.push(DefType::GenericInput(id));
                     Ok(())
                 }
             }
-            Rule::enum_def => {
+            Rule::enum_def | Rule::graphql_enum_def => {
                 let block_def = parse_block_def(pair.clone(), file)?;
                 let id = block_def.name.clone();
                 if self.enums.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "enum", &id.id)))
+                    Err(self.already_defined_error(&id, "enum"))
                 } else {
                     self.enums.insert(id.id.clone(), block_def);
                     self.order.push(DefType::Enum(id));
@@ -254,11 +416,13 @@ impl Spec {
                 self.order.push(DefType::Enum(id));
                 Ok(())
             }
-            Rule::interface_def => {
+            Rule::interface_def | Rule::graphql_interface_def => {
                 let block_def = parse_block_def(pair.clone(), file)?;
                 let id = block_def.name.clone();
-                if self.interfaces.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "interface", &id.id)))
+                if self.interfaces.contains_key(&id.id)
+                    || self.generic_interfaces.contains_key(&id.id)
+                {
+                    Err(self.already_defined_error(&id, "interface"))
                 } else {
                     self.interfaces.insert(id.id.clone(), block_def);
                     self.order.push(DefType::Interface(id));
@@ -272,11 +436,25 @@ impl Spec {
                 self.order.push(DefType::Interface(id));
                 Ok(())
             }
+            Rule::generic_interface_def => {
+                let generic_block_def = parse_generic_block_def(pair.clone(), file)?;
+                let id = generic_block_def.name.clone();
+                if self.generic_interfaces.contains_key(&id.id)
+                    || self.interfaces.contains_key(&id.id)
+                {
+                    Err(self.already_defined_error(&id, "interface"))
+                } else {
+                    self.generic_interfaces
+                        .insert(id.id.clone(), generic_block_def);
+                    self.order.push(DefType::GenericInterface(id));
+                    Ok(())
+                }
+            }
             Rule::scalar_def => {
                 let scalar = parse_scalar(pair.clone(), file)?;
                 let id = scalar.name.clone();
                 if self.scalars.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "scalar", &id.id)))
+                    Err(self.already_defined_error(&id, "scalar"))
                 } else {
                     self.scalars.insert(id.id.clone(), scalar);
                     self.order.push(DefType::Scalar(id));
@@ -293,8 +471,8 @@ impl Spec {
             Rule::union_def => {
                 let union = parse_union(pair.clone(), file)?;
                 let id = union.name.clone();
-                if self.unions.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "union", &id.id)))
+                if self.unions.contains_key(&id.id) || self.generic_unions.contains_key(&id.id) {
+                    Err(self.already_defined_error(&id, "union"))
                 } else {
                     self.unions.insert(id.id.clone(), union);
                     self.order.push(DefType::Union(id));
@@ -308,25 +486,69 @@ impl Spec {
                 self.order.push(DefType::Union(id));
                 Ok(())
             }
+            Rule::generic_union_def => {
+                let generic_union = parse_generic_union(pair.clone(), file)?;
+                let id = generic_union.name.clone();
+                if self.generic_unions.contains_key(&id.id) || self.unions.contains_key(&id.id) {
+                    Err(self.already_defined_error(&id, "union"))
+                } else {
+                    self.generic_unions.insert(id.id.clone(), generic_union);
+                    self.order.push(DefType::GenericUnion(id));
+                    Ok(())
+                }
+            }
             Rule::directive_def => {
                 let directive = parse_directive_def(pair.clone(), file)?;
                 let id = directive.name.clone();
                 if self.directives.contains_key(&id.id) {
-                    Err(Box::new(already_defined_error(pair, "directive", &id.id)))
+                    Err(self.already_defined_error(&id, "directive"))
                 } else {
                     self.directives.insert(id.id.clone(), directive);
                     self.order.push(DefType::Directive(id));
                     Ok(())
                 }
             }
-            _unknown => Err(Box::new(unknown_rule_error(
+            Rule::modifier_def => {
+                let modifier = parse_modifier_def(pair.clone(), file)?;
+                let id = modifier.name.clone();
+                if self.modifiers.contains_key(&id.id) {
+                    Err(self.already_defined_error(&id, "modifier"))
+                } else {
+                    self.modifiers.insert(id.id.clone(), modifier);
+                    self.order.push(DefType::Modifier(id));
+                    Ok(())
+                }
+            }
+            _unknown => Err(unknown_rule_error(
                 pair,
-                "type, input, enum, interface, scalar, union, directive",
-            ))),
+                "type, input, enum, interface, scalar, union, directive, modifier",
+            )),
         }
     }
 }
 
+fn schema_already_defined_error(span: &OwnedSpan, first: &OwnedSpan) -> Box<LocatedError> {
+    span.make_error("schema is already defined")
+        .with_label(first.make_label("first defined here"))
+}
+
+/// Plain `.graphql` files are parsed with the same rules as GraphQXL ones, so the GraphQXL
+/// syntax that can appear inside standard definitions is rejected here.
+fn check_plain_graphql(pair: &Pair<Rule>, file: &str) -> Result<(), Box<dyn Error>> {
+    for inner in pair.clone().into_inner().flatten() {
+        let feature = match inner.as_rule() {
+            Rule::generic => "generic parameters",
+            Rule::generic_object => "generic instantiations",
+            Rule::spread_reference => "spread references",
+            Rule::description_variables => "description variables",
+            _ => continue,
+        };
+        return Err(OwnedSpan::from(inner.as_span(), file)
+            .make_error(&format!("{feature} are not allowed in plain GraphQL files")));
+    }
+    Ok(())
+}
+
 fn check_import_loop(import_stack: &Vec<PathBuf>, span: &OwnedSpan) -> Result<(), Box<dyn Error>> {
     let mut seen = HashSet::new();
     for import in import_stack {
@@ -351,127 +573,346 @@ fn check_import_loop(import_stack: &Vec<PathBuf>, span: &OwnedSpan) -> Result<()
     Ok(())
 }
 
-
-/// Trait for a virtual file system.
-pub trait VirtualFileSystem {
-    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>>;
-    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>>;
-    fn exists(&self, path: &Path) -> bool;
+/// Options that change how a spec is parsed.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct ParseSpecOptions {
+    /// Directories where package-style imports, like `import "@shared/pagination"`,
+    /// are looked up, in order, when they are not relative to the importing file.
+    pub import_roots: Vec<PathBuf>,
+    /// Placed between the namespace of an import like `import "billing" as Billing`
+    /// and the names of the imported definitions.
+    pub namespace_separator: String,
+    /// Definitions whose names start with it are private, the namespace of an import is
+    /// placed after it so that they stay private.
+    pub private_prefix: String,
 }
 
-/// VFS-based version of private_parse_spec
-fn private_parse_spec_vfs<P: AsRef<Path>>(
-    path: P,
-    import_stack: Vec<PathBuf>,
-    already_imported: &mut HashSet<PathBuf>,
+/// Finds the files an import refers to, which can be many if it is a glob.
+fn resolve_import_paths(
+    import: &Import,
+    importer: &Path,
     vfs: &dyn VirtualFileSystem,
-) -> Result<Spec, Box<dyn Error>> {
-    let abs_path = vfs.canonicalize(path.as_ref())?;
-    let file = abs_path.to_str().unwrap();
+    options: &ParseSpecOptions,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let file_dir = importer.parent().unwrap();
+    let glob = match parse_import_glob(&import.file_name) {
+        Ok(glob) => glob,
+        Err(msg) => return Err(import.span.make_error(&msg)),
+    };
+    if let Some(glob) = glob {
+        let mut import_paths: Vec<PathBuf> = vfs
+            .list_files(&file_dir.join(glob.dir), glob.recursive)
+            .map_err(|err| {
+                import
+                    .span
+                    .make_error(&format!("cannot list the files of \"{}\": {err}", glob.dir))
+            })?
+            .into_iter()
+            .filter(|path| path.to_string_lossy().ends_with(GRAPHQXL_EXTENSION))
+            .filter(|path| match vfs.canonicalize(path) {
+                Ok(path) => path != importer,
+                Err(_) => true,
+            })
+            .collect();
+        // sorted, so that the order of the definitions does not depend on the file system
+        import_paths.sort();
+        if import_paths.is_empty() {
+            return Err(import
+                .span
+                .make_error(&format!("\"{}\" does not match any file", import.file_name)));
+        }
+        return Ok(import_paths);
+    }
 
-    let mut spec = Spec::new();
-    if already_imported.contains(&abs_path) {
-        return Ok(spec);
+    match vfs.resolve_import(file_dir, &import.file_name, &options.import_roots) {
+        Some(import_path) => Ok(vec![import_path]),
+        None if is_relative_import(&import.file_name) => {
+            let import_path = file_dir.join(with_graphqxl_extension(&import.file_name));
+            Err(import.span.make_error(
+                format!("file {:?} does not exist", import_path).as_str(),
+            ))
+        }
+        None => Err(import.span.make_error(&format!(
+            "cannot resolve \"{}\", it was not found next to the importing file, in the import roots, or in a graphqxl_modules or node_modules directory",
+            import.file_name
+        ))),
     }
-    let content = vfs.read_to_string(&abs_path)?;
-    let mut pairs = GraphqxlParser::parse(Rule::spec, &content)?;
+}
+
+/// What a file contains by itself, without the content of its imports.
+#[derive(Clone)]
+struct ParsedFile {
+    imports: Vec<Import>,
+    spec: Spec,
+}
+
+/// A file along with the errors of the definitions that were left out of it.
+type ParsedFileWithErrors = (ParsedFile, Vec<Box<dyn Error>>);
+
+struct CachedFile {
+    hash: u64,
+    parsed: ParsedFile,
+    /// Run in which the file was last read, files are only read once per run.
+    run: usize,
+}
+
+/// Files that were already parsed, along with a hash of the content they were parsed
+/// from. It can be kept between compilations, so that only the files that changed
+/// since the previous one are parsed again.
+#[derive(Default)]
+pub struct ParseCache {
+    files: HashMap<PathBuf, CachedFile>,
+    run: usize,
+    misses: usize,
+}
+
+impl ParseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of times a file had to be parsed because it was not cached yet or because
+    /// its content changed.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the file along with the errors of the definitions that were left out of
+    /// it, a file that cannot be parsed at all is an error by itself.
+    fn get(
+        &mut self,
+        abs_path: &Path,
+        vfs: &dyn VirtualFileSystem,
+    ) -> Result<ParsedFileWithErrors, Box<dyn Error>> {
+        if let Some(cached) = self.files.get(abs_path) {
+            if cached.run == self.run {
+                return Ok((cached.parsed.clone(), vec![]));
+            }
+        }
+        let content = vfs.read_to_string(abs_path)?;
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let hash = hasher.finish();
+        if let Some(cached) = self.files.get_mut(abs_path) {
+            if cached.hash == hash {
+                cached.run = self.run;
+                return Ok((cached.parsed.clone(), vec![]));
+            }
+        }
+        self.misses += 1;
+        let (parsed, errors) = parse_file(abs_path, &content)?;
+        // files with errors are not cached, so that they are reported again next time
+        if errors.is_empty() {
+            self.files.insert(
+                abs_path.to_path_buf(),
+                CachedFile {
+                    hash,
+                    parsed: parsed.clone(),
+                    run: self.run,
+                },
+            );
+        }
+        Ok((parsed, errors))
+    }
+
+    /// Drops the files that were not read in the current run, like the ones that are no
+    /// longer imported, so that the cache does not grow forever.
+    fn evict_unused(&mut self) {
+        let run = self.run;
+        self.files.retain(|_, cached| cached.run == run);
+    }
+}
+
+fn parse_file(abs_path: &Path, content: &str) -> Result<ParsedFileWithErrors, Box<dyn Error>> {
+    let file = abs_path.to_str().unwrap();
+    let in_file = |err| LocatedError::in_file(err, file);
+    let (parsed, errors) = private_parse_file(file, content).map_err(in_file)?;
+    Ok((parsed, errors.into_iter().map(in_file).collect()))
+}
+
+/// Parses a file, the definitions that are wrong are left out of it and their errors
+/// are returned along with the rest of the file.
+fn private_parse_file(file: &str, content: &str) -> Result<ParsedFileWithErrors, Box<dyn Error>> {
+    let rule = match is_plain_graphql(file) {
+        true => Rule::graphql_spec,
+        false => Rule::spec,
+    };
+    let mut pairs = GraphqxlParser::parse(rule, content)?;
     let pair = pairs.next().unwrap();
     match pair.as_rule() {
-        Rule::spec => {
+        Rule::spec | Rule::graphql_spec => {
+            if let Rule::graphql_spec = pair.as_rule() {
+                check_plain_graphql(&pair, file)?;
+            }
+            let mut parsed = ParsedFile {
+                imports: vec![],
+                spec: Spec::new(),
+            };
+            // a wrong definition does not prevent the next ones from being checked
+            let mut errors: Vec<Box<dyn Error>> = vec![];
             for child in pair.into_inner() {
                 if let Rule::EOI = &child.as_rule() {
                     // nothing to do here
                 } else if let Rule::import = &child.as_rule() {
-                    let import = parse_import(child.clone(), file)?;
-                    let file_name = if import.file_name.ends_with(".graphqxl") {
-                        import.file_name
-                    } else {
-                        import.file_name + ".graphqxl"
-                    };
-                    let file_dir = abs_path.parent().unwrap();
-                    let import_path = Path::new(file_dir).join(&file_name);
-                    if !vfs.exists(&import_path) {
-                        return Err(import.span.make_error(
-                            format!("file {:?} does not exist", import_path).as_str(),
-                        ));
+                    match parse_import(child, file) {
+                        Ok(import) => parsed.imports.push(import),
+                        Err(err) => errors.push(err),
                     }
-                    let mut stack = import_stack.clone();
-                    stack.push(import_path.clone());
-                    check_import_loop(&stack, &import.span)?;
-                    let imported_spec =
-                        private_parse_spec_vfs(import_path, stack, already_imported, vfs)?;
-                    spec.merge(imported_spec)?;
-                } else {
-                    spec.add(child, file)?;
+                } else if let Err(err) = parsed.spec.add(child, file) {
+                    errors.push(err);
                 }
             }
-            already_imported.insert(abs_path.clone());
-            Ok(spec)
+            Ok((parsed, errors))
         }
-        _unknown => Err(Box::new(unknown_rule_error(pair, "spec"))),
+        _unknown => Err(unknown_rule_error(pair, "spec")),
     }
 }
-pub fn parse_spec_vfs<P: AsRef<Path>>(path: P, vfs: &dyn VirtualFileSystem) -> Result<Spec, Box<dyn Error>> {
-    private_parse_spec_vfs(path, Vec::new(), &mut HashSet::new(), vfs)
-}
 
 fn private_parse_spec<P: AsRef<Path>>(
     path: P,
     import_stack: Vec<PathBuf>,
     already_imported: &mut HashSet<PathBuf>,
+    cache: &mut ParseCache,
+    vfs: &dyn VirtualFileSystem,
+    options: &ParseSpecOptions,
 ) -> Result<Spec, Box<dyn Error>> {
-    let abs_path = fs::canonicalize(path)?;
-    let file = abs_path.to_str().unwrap();
+    let abs_path = vfs.canonicalize(path.as_ref())?;
 
     let mut spec = Spec::new();
     if already_imported.contains(&abs_path) {
         return Ok(spec);
     }
-    let content = fs::read_to_string(&abs_path)?;
-    let mut pairs = GraphqxlParser::parse(Rule::spec, &content)?;
-    let pair = pairs.next().unwrap();
-    match pair.as_rule() {
-        Rule::spec => {
-            for child in pair.into_inner() {
-                if let Rule::EOI = &child.as_rule() {
-                    // nothing to do here
-                } else if let Rule::import = &child.as_rule() {
-                    let import = parse_import(child.clone(), file)?;
-                    let file_name = if import.file_name.ends_with(".graphqxl") {
-                        import.file_name
-                    } else {
-                        import.file_name + ".graphqxl"
-                    };
-                    let file_dir = abs_path.parent().unwrap();
-                    let import_path = Path::new(file_dir).join(&file_name);
-                    if !import_path.exists() {
-                        return Err(import.span.make_error(
-                            format!("file {:?} does not exist", import_path).as_str(),
-                        ));
-                    }
-                    let mut stack = import_stack.clone();
-                    stack.push(import_path.clone());
-                    check_import_loop(&stack, &import.span)?;
-                    let imported_spec = private_parse_spec(import_path, stack, already_imported)?;
-                    spec.merge(imported_spec)?;
-                } else {
-                    spec.add(child, file)?;
-                }
-            }
-            already_imported.insert(abs_path.clone());
-            Ok(spec)
+
+    let (
+        ParsedFile {
+            imports,
+            spec: file_spec,
+        },
+        mut errors,
+    ) = cache.get(&abs_path, vfs)?;
+
+    // the errors of every import are reported, not only the ones of the first that fails
+    for import in imports {
+        let imported = parse_imported_spec(
+            &import,
+            &abs_path,
+            &import_stack,
+            already_imported,
+            cache,
+            vfs,
+            options,
+        )
+        .and_then(|imported_spec| spec.merge_import(&import, imported_spec, options));
+        if let Err(err) = imported {
+            errors.push(err);
         }
-        _unknown => Err(Box::new(unknown_rule_error(pair, "spec"))),
     }
+    if let Err(err) = spec.merge(file_spec) {
+        errors.push(err);
+    }
+    already_imported.insert(abs_path);
+    ErrorList::check(errors)?;
+    Ok(spec)
+}
+
+/// Parses all the files referenced by an import made from `abs_path`.
+fn parse_imported_spec(
+    import: &Import,
+    abs_path: &Path,
+    import_stack: &[PathBuf],
+    already_imported: &mut HashSet<PathBuf>,
+    cache: &mut ParseCache,
+    vfs: &dyn VirtualFileSystem,
+    options: &ParseSpecOptions,
+) -> Result<Spec, Box<dyn Error>> {
+    let mut imported_spec = Spec::new();
+    for import_path in resolve_import_paths(import, abs_path, vfs, options)? {
+        let mut stack = import_stack.to_vec();
+        stack.push(import_path.clone());
+        check_import_loop(&stack, &import.span)?;
+        let file_spec = if import.items.is_some() || import.namespace.is_some() {
+            // the selection or the renaming applies to everything the files
+            // have to offer, so they are parsed on their own
+            private_parse_spec(import_path, stack, &mut HashSet::new(), cache, vfs, options)?
+        } else {
+            private_parse_spec(import_path, stack, already_imported, cache, vfs, options)?
+        };
+        imported_spec.merge(file_spec)?;
+    }
+    Ok(imported_spec)
+}
+
+/// Parses several entrypoints at once, reusing the files of `cache` whose content did
+/// not change. The files the entrypoints have in common are only read once.
+pub fn parse_specs_vfs_with_cache<P: AsRef<Path>>(
+    paths: &[P],
+    vfs: &dyn VirtualFileSystem,
+    options: &ParseSpecOptions,
+    cache: &mut ParseCache,
+) -> Result<Vec<Spec>, Box<dyn Error>> {
+    cache.run += 1;
+    let mut specs = vec![];
+    let mut errors = vec![];
+    for path in paths {
+        match private_parse_spec(path, Vec::new(), &mut HashSet::new(), cache, vfs, options) {
+            Ok(spec) => specs.push(spec),
+            Err(err) => errors.push(err),
+        }
+    }
+    cache.evict_unused();
+    ErrorList::check(errors)?;
+    Ok(specs)
+}
+
+/// Parses several entrypoints at once, the files they have in common are only read
+/// and parsed once.
+pub fn parse_specs_vfs_with_options<P: AsRef<Path>>(
+    paths: &[P],
+    vfs: &dyn VirtualFileSystem,
+    options: &ParseSpecOptions,
+) -> Result<Vec<Spec>, Box<dyn Error>> {
+    parse_specs_vfs_with_cache(paths, vfs, options, &mut ParseCache::new())
+}
+
+pub fn parse_specs_with_options<P: AsRef<Path>>(
+    paths: &[P],
+    options: &ParseSpecOptions,
+) -> Result<Vec<Spec>, Box<dyn Error>> {
+    parse_specs_vfs_with_options(paths, &NativeFileSystem, options)
+}
+
+pub fn parse_spec_vfs_with_options<P: AsRef<Path>>(
+    path: P,
+    vfs: &dyn VirtualFileSystem,
+    options: &ParseSpecOptions,
+) -> Result<Spec, Box<dyn Error>> {
+    let mut specs = parse_specs_vfs_with_options(&[path], vfs, options)?;
+    Ok(specs.remove(0))
+}
+
+pub fn parse_spec_vfs<P: AsRef<Path>>(
+    path: P,
+    vfs: &dyn VirtualFileSystem,
+) -> Result<Spec, Box<dyn Error>> {
+    parse_spec_vfs_with_options(path, vfs, &ParseSpecOptions::default())
+}
+
+pub fn parse_spec_with_options<P: AsRef<Path>>(
+    path: P,
+    options: &ParseSpecOptions,
+) -> Result<Spec, Box<dyn Error>> {
+    parse_spec_vfs_with_options(path, &NativeFileSystem, options)
 }
 
 pub fn parse_spec<P: AsRef<Path>>(path: P) -> Result<Spec, Box<dyn Error>> {
-    private_parse_spec(path, Vec::new(), &mut HashSet::new())
+    parse_spec_with_options(path, &ParseSpecOptions::default())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::MemoryFileSystem;
+    use std::cell::RefCell;
 
     #[test]
     fn test_parses_spec_1() {
@@ -494,4 +935,156 @@ mod tests {
             panic!("Error parsing file: {}", err)
         }
     }
+
+    #[test]
+    fn test_resolves_package_imports() {
+        let spec = parse_spec_with_options(
+            "test_graphqxl_files/packages.graphqxl",
+            &ParseSpecOptions {
+                import_roots: vec![PathBuf::from("test_graphqxl_files/roots")],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(spec.types.contains_key("PageInfo"));
+        assert!(spec.scalars.contains_key("Money"));
+    }
+
+    #[test]
+    fn test_fails_with_unresolved_package_imports() {
+        let err = parse_spec("test_graphqxl_files/packages.graphqxl").unwrap_err();
+        assert!(err.to_string().contains("cannot resolve \"common/money\""))
+    }
+
+    #[test]
+    fn test_expands_globs_in_order_and_without_the_importing_file() {
+        let spec = parse_spec("test_graphqxl_files/globs/index.graphqxl").unwrap();
+        assert_eq!(
+            spec.order,
+            vec![
+                DefType::Type(Identifier::from("A")),
+                DefType::Type(Identifier::from("B")),
+                DefType::Type(Identifier::from("Query")),
+            ]
+        );
+    }
+
+    struct CountingFileSystem(RefCell<HashMap<PathBuf, usize>>);
+
+    impl VirtualFileSystem for CountingFileSystem {
+        fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+            *self.0.borrow_mut().entry(path.to_path_buf()).or_default() += 1;
+            NativeFileSystem.read_to_string(path)
+        }
+
+        fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+            NativeFileSystem.canonicalize(path)
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            NativeFileSystem.exists(path)
+        }
+
+        fn list_files(&self, dir: &Path, recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+            NativeFileSystem.list_files(dir, recursive)
+        }
+    }
+
+    #[test]
+    fn test_parses_shared_imports_once() {
+        let vfs = CountingFileSystem(RefCell::new(HashMap::new()));
+        let specs = parse_specs_vfs_with_options(
+            &[
+                "test_graphqxl_files/entrypoints/public.graphqxl",
+                "test_graphqxl_files/entrypoints/internal.graphqxl",
+            ],
+            &vfs,
+            &ParseSpecOptions::default(),
+        )
+        .unwrap();
+        for spec in specs.iter() {
+            assert!(spec.types.contains_key("User"));
+        }
+        assert_eq!(specs[0].types["Query"].entries.len(), 1);
+        let shared = NativeFileSystem
+            .canonicalize(Path::new("test_graphqxl_files/entrypoints/shared.graphqxl"))
+            .unwrap();
+        assert_eq!(vfs.0.borrow()[&shared], 1);
+        assert_eq!(vfs.0.borrow().len(), 3);
+    }
+
+    #[test]
+    fn test_only_parses_changed_files_again() {
+        let vfs = MemoryFileSystem::from([
+            (
+                "/main.graphqxl",
+                "import \"user\"\nimport \"post\"\n\ntype Query { user: User }",
+            ),
+            ("/user.graphqxl", "type User { id: ID }"),
+            ("/post.graphqxl", "type Post { id: ID }"),
+        ]);
+        let mut cache = ParseCache::new();
+        let options = ParseSpecOptions::default();
+        let parse = |cache: &mut ParseCache| {
+            parse_specs_vfs_with_cache(&["/main.graphqxl"], &vfs, &options, cache)
+                .unwrap()
+                .remove(0)
+        };
+
+        parse(&mut cache);
+        assert_eq!(cache.misses(), 3);
+        parse(&mut cache);
+        assert_eq!(cache.misses(), 3);
+
+        vfs.insert("/post.graphqxl", "type Post { id: ID, title: String }");
+        let spec = parse(&mut cache);
+        assert_eq!(cache.misses(), 4);
+        assert_eq!(spec.types["Post"].entries.len(), 2);
+        assert!(spec.types.contains_key("User"));
+    }
+
+    #[test]
+    fn test_evicts_files_that_are_no_longer_imported() {
+        let vfs = MemoryFileSystem::from([
+            (
+                "/main.graphqxl",
+                "import \"user\"\n\ntype Query { user: User }",
+            ),
+            ("/user.graphqxl", "type User { id: ID }"),
+        ]);
+        let mut cache = ParseCache::new();
+        let options = ParseSpecOptions::default();
+        let mut parse = |main: &str| {
+            vfs.insert("/main.graphqxl", main);
+            parse_specs_vfs_with_cache(&["/main.graphqxl"], &vfs, &options, &mut cache).unwrap();
+            cache.misses()
+        };
+
+        assert_eq!(parse("import \"user\"\n\ntype Query { user: User }"), 2);
+        assert_eq!(parse("type Query { id: ID }"), 3);
+        assert_eq!(parse("import \"user\"\n\ntype Query { user: User }"), 5);
+    }
+
+    #[test]
+    fn test_reports_all_the_errors() {
+        let vfs = MemoryFileSystem::from([
+            (
+                "/main.graphqxl",
+                "import \"missing\"\nimport \"user\"\n\ntype Query { id: ID }\ntype Query { id: ID }",
+            ),
+            ("/user.graphqxl", "type User { id: ID }\ntype User { id: ID }\n"),
+        ]);
+        let err = parse_spec_vfs(Path::new("/main.graphqxl"), &vfs).unwrap_err();
+        let errors = ErrorList::flatten(err);
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0]
+            .to_string()
+            .contains("type \"Query\" is already defined"));
+        assert!(errors[1].to_string().contains("missing"));
+        let located = errors[2].downcast_ref::<LocatedError>().unwrap();
+        assert_eq!(located.file, "/user.graphqxl");
+        assert!(located.message.contains("type \"User\" is already defined"));
+        assert_eq!(located.labels[0].message, "first defined here");
+        assert_eq!(located.labels[0].error.line(), "type User { id: ID }");
+    }
 }
@@ -2,7 +2,7 @@ use crate::ast_description::{parse_description_and_continue, DescriptionAndNext}
 use crate::ast_identifier::{parse_identifier, Identifier};
 use crate::parser::{Rule, RuleError};
 use crate::utils::{unknown_rule_error, OwnedSpan};
-use crate::{parse_directive, Directive};
+use crate::{parse_directive, parse_generic, Directive, Generic};
 use pest::iterators::Pair;
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -10,6 +10,7 @@ pub struct Union {
     pub extend: bool,
     pub span: OwnedSpan,
     pub name: Identifier,
+    pub generic: Option<Generic>,
     pub description: String,
     pub types: Vec<Identifier>,
     pub directives: Vec<Directive>,
@@ -23,6 +24,11 @@ impl Union {
         }
     }
 
+    pub fn generic(&mut self, generic: Generic) -> Self {
+        self.generic = Some(generic);
+        self.clone()
+    }
+
     pub fn description(&mut self, description: &str) -> Self {
         self.description = description.to_string();
         self.clone()
@@ -47,23 +53,24 @@ impl Union {
 fn _parse_union(pair: Pair<Rule>, file: &str, extends: bool) -> Result<Union, Box<RuleError>> {
     let span = OwnedSpan::from(pair.as_span(), file);
     let mut childs = pair.into_inner();
-    // [description?, identifier, ...types]
+    // [description?, identifier, generic?, ...types]
     let DescriptionAndNext(description, next) = parse_description_and_continue(&mut childs, file);
     let name = parse_identifier(next.unwrap(), file)?;
+    let mut generic = None;
     let mut types = Vec::new();
     let mut directives = Vec::new();
     for child in childs {
-        if let Rule::directive = child.as_rule() {
-            directives.push(parse_directive(child, file)?);
-        } else {
-            let name = parse_identifier(child.clone(), file)?;
-            types.push(name);
+        match child.as_rule() {
+            Rule::generic => generic = Some(parse_generic(child, file)?),
+            Rule::directive => directives.push(parse_directive(child, file)?),
+            _ => types.push(parse_identifier(child, file)?),
         }
     }
     Ok(Union {
         extend: extends,
         span,
         name,
+        generic,
         description,
         types,
         directives,
@@ -125,6 +132,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parses_generic_union() {
+        assert_eq!(
+            parse_input("union Result<T> = T | Error"),
+            Ok(Union::build("Result")
+                .generic(Generic::from("T"))
+                .type_("T")
+                .type_("Error"))
+        );
+    }
+
     #[test]
     fn test_accepts_directives() {
         assert_eq!(
@@ -8,6 +8,41 @@ pub enum ValueBasicData {
     Float(f64),
     String(String),
     Boolean(bool),
+    Null,
+    Enum(String),
+}
+
+/// Computes the value of a block string, removing the indentation shared by its lines and
+/// the blank lines around them, as the GraphQL spec says. The result is escaped, as it is
+/// stored the same way as the content of a regular string.
+fn block_string_value(raw: &str) -> String {
+    let raw = raw.replace("\\\"\"\"", "\"\"\"").replace("\r\n", "\n");
+    let lines: Vec<&str> = raw.split('\n').collect();
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => *line,
+            _ => line.get(indent..).unwrap_or(""),
+        })
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    lines[..end]
+        .join("\n")
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
 pub(crate) fn _parse_basic_data(pair: Pair<Rule>) -> Result<ValueBasicData, Box<RuleError>> {
@@ -18,10 +53,16 @@ pub(crate) fn _parse_basic_data(pair: Pair<Rule>) -> Result<ValueBasicData, Box<
             let str = pair.as_str();
             str[1..str.len() - 1].to_string()
         })),
+        Rule::block_string_data => Ok(ValueBasicData::String({
+            let str = pair.as_str();
+            block_string_value(&str[3..str.len() - 3])
+        })),
         Rule::boolean_data => Ok(ValueBasicData::Boolean(pair.as_str() == "true")),
+        Rule::null_data => Ok(ValueBasicData::Null),
+        Rule::enum_data => Ok(ValueBasicData::Enum(pair.as_str().to_string())),
         _unknown => Err(unknown_rule_error(
             pair,
-            "int_data, float_data, string_data, boolean_data",
+            "int_data, float_data, string_data, boolean_data, null_data, enum_data",
         )),
     }
 }
@@ -91,4 +132,42 @@ mod tests {
     fn test_invalid_boolean() {
         parse_input("\"true").unwrap_err();
     }
+
+    #[test]
+    fn test_negative_numbers() {
+        assert_eq!(parse_input("-1").unwrap(), ValueBasicData::Int(-1));
+        assert_eq!(parse_input("-1.5").unwrap(), ValueBasicData::Float(-1.5));
+    }
+
+    #[test]
+    fn test_float_exponent() {
+        assert_eq!(parse_input("1e3").unwrap(), ValueBasicData::Float(1000.0));
+        assert_eq!(parse_input("1.5E-1").unwrap(), ValueBasicData::Float(0.15));
+    }
+
+    #[test]
+    fn test_block_string() {
+        let string = parse_input("\"\"\"\n    first \"line\"\n      second\n  \"\"\"").unwrap();
+        assert_eq!(
+            string,
+            ValueBasicData::String("first \\\"line\\\"\\n  second".to_string())
+        )
+    }
+
+    #[test]
+    fn test_null() {
+        assert_eq!(parse_input("null").unwrap(), ValueBasicData::Null);
+    }
+
+    #[test]
+    fn test_enum() {
+        assert_eq!(
+            parse_input("nullable").unwrap(),
+            ValueBasicData::Enum("nullable".to_string())
+        );
+        assert_eq!(
+            parse_input("ASC").unwrap(),
+            ValueBasicData::Enum("ASC".to_string())
+        );
+    }
 }
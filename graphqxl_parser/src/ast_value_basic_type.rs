@@ -1,6 +1,7 @@
+use crate::ast_generic_call::parse_generic_call;
 use crate::parser::{Rule, RuleError};
 use crate::utils::unknown_rule_error;
-use crate::{Identifier, OwnedSpan};
+use crate::{ExpandableRef, Identifier, OwnedSpan};
 use pest::iterators::Pair;
 use std::fmt::{Display, Formatter};
 
@@ -11,19 +12,19 @@ pub enum ValueBasicType {
     Boolean,
     String,
     Object(Identifier),
+    GenericObject(Box<ExpandableRef>),
 }
 
 impl Display for ValueBasicType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", {
-            match self {
-                ValueBasicType::Int => "Int",
-                ValueBasicType::Float => "Float",
-                ValueBasicType::Boolean => "Boolean",
-                ValueBasicType::String => "String",
-                ValueBasicType::Object(id) => &id.id,
-            }
-        })
+        match self {
+            ValueBasicType::Int => write!(f, "Int"),
+            ValueBasicType::Float => write!(f, "Float"),
+            ValueBasicType::Boolean => write!(f, "Boolean"),
+            ValueBasicType::String => write!(f, "String"),
+            ValueBasicType::Object(id) => write!(f, "{}", id.id),
+            ValueBasicType::GenericObject(expandable_ref) => write!(f, "{expandable_ref}"),
+        }
     }
 }
 
@@ -37,9 +38,24 @@ fn _parse_value_basic_type(pair: Pair<Rule>, file: &str) -> Result<ValueBasicTyp
             id: pair.as_str().to_string(),
             span: OwnedSpan::from(pair.as_span(), file),
         })),
+        Rule::generic_object => {
+            let span = OwnedSpan::from(pair.as_span(), file);
+            let mut childs = pair.into_inner();
+            let object = childs.next().unwrap();
+            let identifier = Identifier {
+                id: object.as_str().to_string(),
+                span: OwnedSpan::from(object.as_span(), file),
+            };
+            let generic_call = parse_generic_call(childs.next().unwrap(), file)?;
+            Ok(ValueBasicType::GenericObject(Box::new(ExpandableRef {
+                span,
+                identifier,
+                generic_call: Some(generic_call),
+            })))
+        }
         _unknown => Err(unknown_rule_error(
             pair,
-            "int, float, string, boolean or object",
+            "int, float, string, boolean, object or generic_object",
         )),
     }
 }
@@ -58,6 +74,7 @@ pub(crate) fn parse_value_basic_type(
 mod tests {
     use super::*;
     use crate::utils::parse_full_input;
+    use crate::ValueType;
 
     fn parse_input(input: &str) -> Result<ValueBasicType, Box<RuleError>> {
         parse_full_input(input, Rule::value_basic_type, parse_value_basic_type)
@@ -99,6 +116,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generic_object() {
+        assert_eq!(
+            parse_input("Connection<User>"),
+            Ok(ValueBasicType::GenericObject(Box::new(
+                ExpandableRef::from("Connection")
+                    .generic_arg(ValueType::object(Identifier::from("User")))
+            )))
+        );
+    }
+
+    #[test]
+    fn test_nested_generic_object() {
+        assert_eq!(
+            parse_input("Page<Edge<User>, String!>"),
+            Ok(ValueBasicType::GenericObject(Box::new(
+                ExpandableRef::from("Page")
+                    .generic_arg(ValueType::generic_object(
+                        ExpandableRef::from("Edge")
+                            .generic_arg(ValueType::object(Identifier::from("User")))
+                    ))
+                    .generic_arg(ValueType::string().non_nullable())
+            )))
+        );
+    }
+
+    #[test]
+    fn test_displays_generic_object() {
+        assert_eq!(
+            parse_input("Page<Edge<User>, String!>")
+                .unwrap()
+                .to_string(),
+            "Page<Edge<User>, String!>"
+        );
+    }
+
     #[test]
     fn test_invalid_1() {
         parse_input("1DoNotStartWithNumber").unwrap_err();
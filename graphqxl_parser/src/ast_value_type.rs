@@ -1,8 +1,10 @@
 use crate::ast_value_basic_type::{parse_value_basic_type, ValueBasicType};
 use crate::parser::{Rule, RuleError};
 use crate::utils::unknown_rule_error;
-use crate::{Identifier, OwnedSpan};
+use crate::{ExpandableRef, Identifier, OwnedSpan};
 use pest::iterators::Pair;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ValueType {
@@ -44,6 +46,10 @@ impl ValueType {
         Self::build(ValueBasicType::Object(identifier))
     }
 
+    pub fn generic_object(expandable_ref: ExpandableRef) -> Self {
+        Self::build(ValueBasicType::GenericObject(Box::new(expandable_ref)))
+    }
+
     pub fn non_nullable(&self) -> Self {
         ValueType::NonNullable(Box::new(self.clone()), self.span().clone())
     }
@@ -60,7 +66,7 @@ impl ValueType {
         }
     }
 
-    pub fn replace_basic_type(&mut self, value: ValueType) -> Result<(), Box<RuleError>> {
+    pub fn replace_basic_type(&mut self, value: ValueType) -> Result<(), Box<dyn Error>> {
         if let ValueType::NonNullable(_, _) = value {
             if let ValueType::NonNullable(_, _) = self {
                 return Err(value.span().make_error(
@@ -77,6 +83,16 @@ impl ValueType {
     }
 }
 
+impl Display for ValueType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueType::Basic(basic, _) => write!(f, "{basic}"),
+            ValueType::Array(inner, _) => write!(f, "[{inner}]"),
+            ValueType::NonNullable(inner, _) => write!(f, "{inner}!"),
+        }
+    }
+}
+
 pub(crate) fn parse_value_type(pair: Pair<Rule>, file: &str) -> Result<ValueType, Box<RuleError>> {
     let span = OwnedSpan::from(pair.as_span(), file);
     match pair.as_rule() {
@@ -184,6 +200,18 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_displays_value_type() {
+        assert_eq!(
+            ValueType::int()
+                .non_nullable()
+                .array()
+                .non_nullable()
+                .to_string(),
+            "[Int!]!"
+        )
+    }
+
     #[test]
     fn test_not_parses_double_nullable() {
         parse_input("[Int!!]").unwrap_err();
@@ -15,10 +15,13 @@ mod ast_function_call;
 mod ast_generic;
 mod ast_generic_block_def;
 mod ast_generic_call;
+mod ast_generic_constraint;
+mod ast_generic_union;
 mod ast_identifier;
 mod ast_implements;
 mod ast_import;
 mod ast_modified_ref;
+mod ast_modifier_def;
 mod ast_scalar;
 mod ast_schema;
 mod ast_spec;
@@ -28,13 +31,16 @@ mod ast_value_basic_type;
 mod ast_value_data;
 mod ast_value_type;
 mod parser;
+mod spec_references;
 mod utils;
+mod virtual_file_system;
 
 pub use ast_arguments::*;
 pub use ast_block_def::*;
 pub use ast_block_field::*;
 pub use ast_expandable_ref::*;
 pub use ast_modified_ref::*;
+pub use ast_modifier_def::*;
 // pub use ast_description::*;
 pub use ast_directive::*;
 pub use ast_directive_def::*;
@@ -43,7 +49,10 @@ pub use ast_function_call::*;
 pub use ast_generic::*;
 pub use ast_generic_block_def::*;
 pub use ast_generic_call::*;
+pub use ast_generic_constraint::*;
+pub use ast_generic_union::*;
 pub use ast_identifier::*;
+pub use ast_import::*;
 pub use ast_implements::*;
 pub use ast_scalar::*;
 pub use ast_schema::*;
@@ -53,4 +62,5 @@ pub use ast_value_basic_data::*;
 pub use ast_value_basic_type::*;
 pub use ast_value_data::*;
 pub use ast_value_type::*;
-pub use utils::OwnedSpan;
+pub use utils::{ErrorList, Label, LocatedError, OwnedSpan};
+pub use virtual_file_system::*;
@@ -0,0 +1,574 @@
+use crate::{
+    Argument, BlockDef, BlockEntry, BlockField, DefType, Directive, DirectiveDef, ExpandableRef,
+    Generic, GenericBlockDef, GenericConstraintItem, GenericUnion, Identifier, ModifiedRef,
+    ModifierDef, Scalar, Schema, Spec, Union, ValueBasicType, ValueType,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Callback that receives every identifier that references another definition by name.
+pub(crate) type Visit<'a> = dyn FnMut(&mut Identifier) + 'a;
+
+fn visit_value_type(value_type: &mut ValueType, visit: &mut Visit) {
+    match value_type {
+        ValueType::Basic(ValueBasicType::Object(identifier), _) => visit(identifier),
+        ValueType::Basic(ValueBasicType::GenericObject(expandable_ref), _) => {
+            visit_expandable_ref(expandable_ref, visit)
+        }
+        ValueType::Basic(_, _) => {}
+        ValueType::Array(inner, _) | ValueType::NonNullable(inner, _) => {
+            visit_value_type(inner, visit)
+        }
+    }
+}
+
+fn visit_expandable_ref(expandable_ref: &mut ExpandableRef, visit: &mut Visit) {
+    visit(&mut expandable_ref.identifier);
+    if let Some(generic_call) = &mut expandable_ref.generic_call {
+        for arg in generic_call.args.iter_mut() {
+            visit_value_type(arg, visit);
+        }
+    }
+}
+
+fn visit_modified_ref(modified_ref: &mut ModifiedRef, visit: &mut Visit) {
+    match modified_ref {
+        ModifiedRef::Required(inner, _)
+        | ModifiedRef::Optional(inner, _)
+        | ModifiedRef::DeepRequired(inner, _)
+        | ModifiedRef::DeepOptional(inner, _)
+        | ModifiedRef::Pick(inner, _, _)
+        | ModifiedRef::Omit(inner, _, _) => visit_modified_ref(inner, visit),
+        ModifiedRef::ExpandableRef(expandable_ref) => visit_expandable_ref(expandable_ref, visit),
+    }
+}
+
+fn visit_directives(directives: &mut [Directive], visit: &mut Visit) {
+    for directive in directives.iter_mut() {
+        visit(&mut directive.name);
+    }
+}
+
+fn visit_argument(argument: &mut Argument, visit: &mut Visit) {
+    // default identifiers are enum values, not definitions
+    visit_value_type(&mut argument.value_type, visit);
+    visit_directives(&mut argument.directives, visit);
+}
+
+fn visit_block_field(block_field: &mut BlockField, visit: &mut Visit) {
+    if let Some(value_type) = &mut block_field.value_type {
+        visit_value_type(value_type, visit);
+    }
+    for argument in block_field.args.iter_mut() {
+        visit_argument(argument, visit);
+    }
+    visit_directives(&mut block_field.directives, visit);
+}
+
+fn visit_generic(generic: &mut Generic, visit: &mut Visit) {
+    for default in generic.defaults.values_mut() {
+        visit_value_type(default, visit);
+    }
+    for constraint in generic.constraints.values_mut() {
+        for item in constraint.items.iter_mut() {
+            if let GenericConstraintItem::Implements(implements) = item {
+                implements.interfaces.iter_mut().for_each(&mut *visit);
+            }
+        }
+    }
+}
+
+/// Generic parameters shadow any definition with the same name inside the definition
+/// that declares them, so they are not references.
+fn generic_params(generic: Option<&Generic>) -> Vec<String> {
+    match generic {
+        Some(generic) => generic.args.iter().map(|arg| arg.id.clone()).collect(),
+        None => vec![],
+    }
+}
+
+fn visit_block_def(block_def: &mut BlockDef, visit: &mut Visit) {
+    let params = generic_params(block_def.generic.as_ref());
+    let visit = &mut |identifier: &mut Identifier| {
+        if !params.contains(&identifier.id) {
+            visit(identifier)
+        }
+    };
+    if let Some(generic) = &mut block_def.generic {
+        visit_generic(generic, visit);
+    }
+    if let Some(implements) = &mut block_def.implements {
+        implements.interfaces.iter_mut().for_each(&mut *visit);
+    }
+    for entry in block_def.entries.iter_mut() {
+        match entry {
+            BlockEntry::Field(block_field) => visit_block_field(block_field, visit),
+            BlockEntry::SpreadRef(modified_ref) => visit_modified_ref(modified_ref, visit),
+        }
+    }
+    visit_directives(&mut block_def.directives, visit);
+}
+
+fn visit_generic_block_def(generic_block_def: &mut GenericBlockDef, visit: &mut Visit) {
+    visit_modified_ref(&mut generic_block_def.modified_ref, visit);
+    visit_directives(&mut generic_block_def.directives, visit);
+}
+
+fn visit_scalar(scalar: &mut Scalar, visit: &mut Visit) {
+    visit_directives(&mut scalar.directives, visit);
+}
+
+fn visit_union(union: &mut Union, visit: &mut Visit) {
+    let params = generic_params(union.generic.as_ref());
+    let visit = &mut |identifier: &mut Identifier| {
+        if !params.contains(&identifier.id) {
+            visit(identifier)
+        }
+    };
+    if let Some(generic) = &mut union.generic {
+        visit_generic(generic, visit);
+    }
+    union.types.iter_mut().for_each(&mut *visit);
+    visit_directives(&mut union.directives, visit);
+}
+
+fn visit_generic_union(generic_union: &mut GenericUnion, visit: &mut Visit) {
+    visit_expandable_ref(&mut generic_union.expandable_ref, visit);
+    visit_directives(&mut generic_union.directives, visit);
+}
+
+fn visit_directive_def(directive_def: &mut DirectiveDef, visit: &mut Visit) {
+    for argument in directive_def.arguments.iter_mut() {
+        visit_argument(argument, visit);
+    }
+}
+
+fn visit_modifier_def(modifier_def: &mut ModifierDef, visit: &mut Visit) {
+    let params = generic_params(Some(&modifier_def.generic));
+    let visit = &mut |identifier: &mut Identifier| {
+        if !params.contains(&identifier.id) {
+            visit(identifier)
+        }
+    };
+    visit_generic(&mut modifier_def.generic, visit);
+    visit_modified_ref(&mut modifier_def.modified_ref, visit);
+}
+
+fn visit_schema(schema: &mut Schema, visit: &mut Visit) {
+    for identifier in [
+        &mut schema.query,
+        &mut schema.mutation,
+        &mut schema.subscription,
+    ] {
+        if !identifier.id.is_empty() {
+            visit(identifier);
+        }
+    }
+    visit_directives(&mut schema.directives, visit);
+}
+
+impl Spec {
+    /// The name of the definition referred by an entry of the order, which for extensions
+    /// is not the same as the key they are stored with.
+    pub(crate) fn def_name(&self, def: &DefType) -> Option<&Identifier> {
+        match def {
+            DefType::Type(key) => self.types.get(&key.id).map(|def| &def.name),
+            DefType::GenericType(key) => self.generic_types.get(&key.id).map(|def| &def.name),
+            DefType::Input(key) => self.inputs.get(&key.id).map(|def| &def.name),
+            DefType::GenericInput(key) => self.generic_inputs.get(&key.id).map(|def| &def.name),
+            DefType::Enum(key) => self.enums.get(&key.id).map(|def| &def.name),
+            DefType::Interface(key) => self.interfaces.get(&key.id).map(|def| &def.name),
+            DefType::GenericInterface(key) => {
+                self.generic_interfaces.get(&key.id).map(|def| &def.name)
+            }
+            DefType::Scalar(key) => self.scalars.get(&key.id).map(|def| &def.name),
+            DefType::Union(key) => self.unions.get(&key.id).map(|def| &def.name),
+            DefType::GenericUnion(key) => self.generic_unions.get(&key.id).map(|def| &def.name),
+            DefType::Directive(key) => self.directives.get(&key.id).map(|def| &def.name),
+            DefType::Modifier(key) => self.modifiers.get(&key.id).map(|def| &def.name),
+            DefType::Schema(_) => None,
+        }
+    }
+
+    /// Visits the name of the definition referred by an entry of the order.
+    fn visit_def_name(&mut self, def: &DefType, visit: &mut Visit) {
+        match def {
+            DefType::Type(key) => self.types.get_mut(&key.id).map(|def| visit(&mut def.name)),
+            DefType::GenericType(key) => self
+                .generic_types
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::Input(key) => self.inputs.get_mut(&key.id).map(|def| visit(&mut def.name)),
+            DefType::GenericInput(key) => self
+                .generic_inputs
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::Enum(key) => self.enums.get_mut(&key.id).map(|def| visit(&mut def.name)),
+            DefType::Interface(key) => self
+                .interfaces
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::GenericInterface(key) => self
+                .generic_interfaces
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::Scalar(key) => self
+                .scalars
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::Union(key) => self.unions.get_mut(&key.id).map(|def| visit(&mut def.name)),
+            DefType::GenericUnion(key) => self
+                .generic_unions
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::Directive(key) => self
+                .directives
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::Modifier(key) => self
+                .modifiers
+                .get_mut(&key.id)
+                .map(|def| visit(&mut def.name)),
+            DefType::Schema(_) => None,
+        };
+    }
+
+    /// Visits the references to other definitions made by the definition referred
+    /// by an entry of the order.
+    pub(crate) fn visit_def_refs(&mut self, def: &DefType, visit: &mut Visit) {
+        match def {
+            DefType::Type(key) => self
+                .types
+                .get_mut(&key.id)
+                .map(|def| visit_block_def(def, visit)),
+            DefType::GenericType(key) => self
+                .generic_types
+                .get_mut(&key.id)
+                .map(|def| visit_generic_block_def(def, visit)),
+            DefType::Input(key) => self
+                .inputs
+                .get_mut(&key.id)
+                .map(|def| visit_block_def(def, visit)),
+            DefType::GenericInput(key) => self
+                .generic_inputs
+                .get_mut(&key.id)
+                .map(|def| visit_generic_block_def(def, visit)),
+            DefType::Enum(key) => self
+                .enums
+                .get_mut(&key.id)
+                .map(|def| visit_block_def(def, visit)),
+            DefType::Interface(key) => self
+                .interfaces
+                .get_mut(&key.id)
+                .map(|def| visit_block_def(def, visit)),
+            DefType::GenericInterface(key) => self
+                .generic_interfaces
+                .get_mut(&key.id)
+                .map(|def| visit_generic_block_def(def, visit)),
+            DefType::Scalar(key) => self
+                .scalars
+                .get_mut(&key.id)
+                .map(|def| visit_scalar(def, visit)),
+            DefType::Union(key) => self
+                .unions
+                .get_mut(&key.id)
+                .map(|def| visit_union(def, visit)),
+            DefType::GenericUnion(key) => self
+                .generic_unions
+                .get_mut(&key.id)
+                .map(|def| visit_generic_union(def, visit)),
+            DefType::Directive(key) => self
+                .directives
+                .get_mut(&key.id)
+                .map(|def| visit_directive_def(def, visit)),
+            DefType::Modifier(key) => self
+                .modifiers
+                .get_mut(&key.id)
+                .map(|def| visit_modifier_def(def, visit)),
+            DefType::Schema(key) => self
+                .schemas
+                .get_mut(key)
+                .map(|def| visit_schema(def, visit)),
+        };
+    }
+
+    /// Visits the names of all the definitions and all the references between them.
+    pub(crate) fn visit_all_names(&mut self, visit: &mut Visit) {
+        for def in self.order.clone().iter() {
+            self.visit_def_name(def, visit);
+            self.visit_def_refs(def, visit);
+        }
+    }
+
+    /// Renames the given definitions, along with all the references to them.
+    pub(crate) fn rename(&mut self, renames: &HashMap<String, String>) {
+        self.visit_all_names(&mut |identifier| {
+            if let Some(renamed) = renames.get(&identifier.id) {
+                identifier.id = renamed.clone();
+            }
+        });
+        // definitions are stored by their name, except extensions, which can stay as they
+        // are. The maps are built again at once, so that swapping two names loses nothing
+        macro_rules! rekey {
+            ($($map:ident),*) => {
+                $(
+                    self.$map = std::mem::take(&mut self.$map)
+                        .into_iter()
+                        .map(|(key, def)| (renames.get(&key).cloned().unwrap_or(key), def))
+                        .collect();
+                )*
+            };
+        }
+        rekey!(
+            types,
+            generic_types,
+            inputs,
+            generic_inputs,
+            enums,
+            interfaces,
+            generic_interfaces,
+            scalars,
+            unions,
+            generic_unions,
+            directives,
+            modifiers
+        );
+        for def in self.order.iter_mut() {
+            match def {
+                DefType::Type(key)
+                | DefType::GenericType(key)
+                | DefType::Input(key)
+                | DefType::GenericInput(key)
+                | DefType::Enum(key)
+                | DefType::Interface(key)
+                | DefType::GenericInterface(key)
+                | DefType::Scalar(key)
+                | DefType::Union(key)
+                | DefType::GenericUnion(key)
+                | DefType::Directive(key)
+                | DefType::Modifier(key) => {
+                    if let Some(renamed) = renames.get(&key.id) {
+                        key.id = renamed.clone();
+                    }
+                }
+                DefType::Schema(_) => {}
+            }
+        }
+    }
+
+    /// Prefixes the names of all the definitions, except directives, which are usually
+    /// meant to be understood by other tools, along with all the references to them.
+    /// Private definitions keep the `private_prefix` in front of the new one.
+    pub(crate) fn prefix(&mut self, prefix: &str, private_prefix: &str) {
+        let mut renames = HashMap::new();
+        for def in self.order.iter() {
+            if let DefType::Directive(_) = def {
+                continue;
+            }
+            if let Some(name) = self.def_name(def) {
+                let renamed = match name.id.strip_prefix(private_prefix) {
+                    Some(rest) if !private_prefix.is_empty() => {
+                        format!("{private_prefix}{prefix}{rest}")
+                    }
+                    _ => format!("{prefix}{}", name.id),
+                };
+                renames.insert(name.id.clone(), renamed);
+            }
+        }
+        self.rename(&renames);
+    }
+
+    pub(crate) fn defines(&self, name: &str) -> bool {
+        self.order
+            .iter()
+            .any(|def| matches!(self.def_name(def), Some(def_name) if def_name.id == name))
+    }
+
+    /// Keeps only the given definitions and the ones they need, because they reference
+    /// them directly or through other definitions. Schemas are never kept.
+    pub(crate) fn select(&mut self, names: &[&str]) {
+        let mut refs: HashMap<String, Vec<String>> = HashMap::new();
+        for def in self.order.clone().iter() {
+            let name = match self.def_name(def) {
+                Some(name) => name.id.clone(),
+                None => continue,
+            };
+            let mut def_refs = vec![];
+            self.visit_def_refs(def, &mut |identifier| def_refs.push(identifier.id.clone()));
+            refs.entry(name).or_default().extend(def_refs);
+        }
+
+        let mut selected: HashSet<String> = HashSet::new();
+        let mut pending: VecDeque<String> = names.iter().map(|name| name.to_string()).collect();
+        while let Some(name) = pending.pop_front() {
+            if selected.contains(&name) {
+                continue;
+            }
+            if let Some(def_refs) = refs.get(&name) {
+                pending.extend(def_refs.iter().cloned());
+                selected.insert(name);
+            }
+        }
+
+        let order = std::mem::take(&mut self.order);
+        self.order = order
+            .into_iter()
+            .filter(|def| matches!(self.def_name(def), Some(name) if selected.contains(&name.id)))
+            .collect();
+        self.types.retain(|_, def| selected.contains(&def.name.id));
+        self.generic_types
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.inputs.retain(|_, def| selected.contains(&def.name.id));
+        self.generic_inputs
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.enums.retain(|_, def| selected.contains(&def.name.id));
+        self.interfaces
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.generic_interfaces
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.scalars
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.unions.retain(|_, def| selected.contains(&def.name.id));
+        self.generic_unions
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.directives
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.modifiers
+            .retain(|_, def| selected.contains(&def.name.id));
+        self.schemas.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{GraphqxlParser, Rule};
+    use pest::Parser;
+
+    fn parse_input(input: &str) -> Spec {
+        let pair = GraphqxlParser::parse(Rule::spec, input)
+            .unwrap()
+            .next()
+            .unwrap();
+        let mut spec = Spec::default();
+        for child in pair.into_inner() {
+            if child.as_rule() != Rule::EOI {
+                spec.add(child, "").unwrap();
+            }
+        }
+        spec
+    }
+
+    #[test]
+    fn test_renames_definitions_and_references() {
+        let mut spec = parse_input(
+            "type Product { id: ID! } type Order { products: [Product!]! } union Item = Product | Order",
+        );
+        spec.rename(&HashMap::from([(
+            "Product".to_string(),
+            "CatalogProduct".to_string(),
+        )]));
+        assert!(spec.types.contains_key("CatalogProduct"));
+        assert!(!spec.types.contains_key("Product"));
+        assert_eq!(spec.types["CatalogProduct"].name.id, "CatalogProduct");
+        assert_eq!(
+            spec.types["Order"].entries[0],
+            BlockEntry::Field(Box::new(
+                BlockField::build("products").value_type(
+                    ValueType::object(Identifier::from("CatalogProduct"))
+                        .non_nullable()
+                        .array()
+                        .non_nullable()
+                )
+            ))
+        );
+        assert_eq!(spec.unions["Item"].types[0].id, "CatalogProduct");
+        assert_eq!(
+            spec.order[0],
+            DefType::Type(Identifier::from("CatalogProduct"))
+        );
+    }
+
+    #[test]
+    fn test_swaps_names() {
+        let mut spec = parse_input("type A { b: B } type B { id: ID! }");
+        spec.rename(&HashMap::from([
+            ("A".to_string(), "B".to_string()),
+            ("B".to_string(), "A".to_string()),
+        ]));
+        assert_eq!(spec.types["B"].name.id, "B");
+        assert_eq!(
+            spec.types["B"].entries[0],
+            BlockEntry::Field(Box::new(
+                BlockField::build("b").value_type(ValueType::object(Identifier::from("A")))
+            ))
+        );
+        assert_eq!(spec.types["A"].name.id, "A");
+        assert_eq!(spec.types["A"].entries.len(), 1);
+        assert_eq!(spec.order[0], DefType::Type(Identifier::from("B")));
+    }
+
+    #[test]
+    fn test_prefixes_everything_but_directives() {
+        let mut spec = parse_input(
+            "directive @tag on OBJECT scalar Money type Invoice @tag { total: Money! } schema { query: Invoice }",
+        );
+        spec.prefix("Billing", "_");
+        assert_eq!(spec.scalars["BillingMoney"].name.id, "BillingMoney");
+        let invoice = &spec.types["BillingInvoice"];
+        assert_eq!(invoice.directives[0].name.id, "tag");
+        assert_eq!(
+            invoice.entries[0],
+            BlockEntry::Field(Box::new(BlockField::build("total").value_type(
+                ValueType::object(Identifier::from("BillingMoney")).non_nullable()
+            )))
+        );
+        assert_eq!(spec.schemas["schema"].query.id, "BillingInvoice");
+    }
+
+    #[test]
+    fn test_keeps_private_definitions_private() {
+        let mut spec = parse_input("type _Base { id: ID! } type Invoice { ..._Base }");
+        spec.prefix("Billing", "_");
+        assert!(spec.types.contains_key("_BillingBase"));
+        assert_eq!(
+            spec.types["BillingInvoice"].entries[0],
+            BlockEntry::SpreadRef(ModifiedRef::build("_BillingBase"))
+        );
+    }
+
+    #[test]
+    fn test_does_not_rename_generic_parameters() {
+        let mut spec = parse_input("type T { id: ID! } type Box<T> { value: T }");
+        spec.rename(&HashMap::from([("T".to_string(), "Other".to_string())]));
+        assert_eq!(
+            spec.types["Box"].entries[0],
+            BlockEntry::Field(Box::new(
+                BlockField::build("value").value_type(ValueType::object(Identifier::from("T")))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_selects_definitions_and_their_dependencies() {
+        let mut spec = parse_input(
+            "
+            directive @tag on FIELD_DEFINITION
+            type Address { street: String! }
+            type User { address: Address! @tag }
+            extend type User { name: String! }
+            type Unused { id: ID! }
+            schema { query: User }
+            ",
+        );
+        spec.select(&["User"]);
+        let mut names: Vec<&str> = spec
+            .order
+            .iter()
+            .map(|def| spec.def_name(def).unwrap().id.as_str())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Address", "User", "User", "tag"]);
+        assert!(spec.schemas.is_empty());
+        assert!(!spec.types.contains_key("Unused"));
+    }
+}
@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Several errors found in the same run, so that they can all be fixed at once. It
+/// shows each one of them separated by a blank line.
+#[derive(Debug)]
+pub struct ErrorList {
+    pub errors: Vec<Box<dyn Error>>,
+}
+
+impl ErrorList {
+    /// Fails if there is any error, with the error itself if there is only one. Errors
+    /// that are lists are flattened, and the ones that show the same are only kept once,
+    /// as a file with errors can be reached through several imports.
+    pub fn check(errors: Vec<Box<dyn Error>>) -> Result<(), Box<dyn Error>> {
+        let mut flattened: Vec<Box<dyn Error>> = vec![];
+        for err in errors.into_iter().flat_map(Self::flatten) {
+            let shown = err.to_string();
+            if !flattened.iter().any(|other| other.to_string() == shown) {
+                flattened.push(err);
+            }
+        }
+        match flattened.len() {
+            0 => Ok(()),
+            1 => Err(flattened.remove(0)),
+            _ => Err(Box::new(Self { errors: flattened })),
+        }
+    }
+
+    /// Splits an error into the ones it is made of, if it is a list.
+    pub fn flatten(err: Box<dyn Error>) -> Vec<Box<dyn Error>> {
+        match err.downcast::<ErrorList>() {
+            Ok(list) => list.errors.into_iter().flat_map(Self::flatten).collect(),
+            Err(err) => vec![err],
+        }
+    }
+}
+
+impl Display for ErrorList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, err) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n\n")?;
+            }
+            err.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ErrorList {}
@@ -0,0 +1,251 @@
+use crate::parser::RuleError;
+use pest::error::{ErrorVariant, InputLocation, LineColLocation};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// A place related to an error other than the one it points to, like the previous
+/// definition of something that is defined twice.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub file: String,
+    pub message: String,
+    pub error: RuleError,
+}
+
+/// An error that points to a place of a file, it keeps the file and the message apart
+/// so that they can be reported on their own. It shows the code it points to, along
+/// with its labels and notes.
+#[derive(Debug, Clone)]
+pub struct LocatedError {
+    pub file: String,
+    pub message: String,
+    pub error: RuleError,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl LocatedError {
+    /// Attaches `file` to the pest errors that were raised while parsing it, other
+    /// errors are returned as they are.
+    pub(crate) fn in_file(err: Box<dyn Error>, file: &str) -> Box<dyn Error> {
+        match err.downcast::<RuleError>() {
+            Ok(error) => Box::new(Self {
+                file: file.to_string(),
+                message: error.variant.message().to_string(),
+                error: *error,
+                labels: vec![],
+                notes: vec![],
+            }),
+            Err(err) => err,
+        }
+    }
+
+    pub fn with_label(mut self: Box<Self>, label: Label) -> Box<Self> {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_note(mut self: Box<Self>, note: &str) -> Box<Self> {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// Byte offsets where the error starts and ends.
+    pub fn offsets(&self) -> (usize, usize) {
+        match self.error.location {
+            InputLocation::Pos(offset) => (offset, offset),
+            InputLocation::Span(span) => span,
+        }
+    }
+
+    /// Lines and columns, starting at 1, where the error starts and ends.
+    pub fn line_cols(&self) -> ((usize, usize), (usize, usize)) {
+        line_cols(&self.error)
+    }
+
+    /// Whether the file does not follow the grammar, as opposed to the errors found
+    /// in the definitions it contains.
+    pub fn is_syntax_error(&self) -> bool {
+        matches!(self.error.variant, ErrorVariant::ParsingError { .. })
+    }
+
+    /// Paints the first line of a rendered error, which is made of its level and its
+    /// message, like `error: something is wrong`.
+    pub fn paint_header(header: &str, color: bool) -> String {
+        match (color, header.split_once(": ")) {
+            (true, Some((level, message))) => format!(
+                "{}{level}{RESET}{BOLD}: {message}{RESET}",
+                level_style(level)
+            ),
+            _ => header.to_string(),
+        }
+    }
+
+    /// Shows the error with the code it points to. `level` is shown before the message,
+    /// usually `error` or `warning`, and `color` adds ANSI colors for terminals.
+    pub fn render(&self, level: &str, color: bool) -> String {
+        let paint = |style: &str, text: &str| match color {
+            true => format!("{style}{text}{RESET}"),
+            false => text.to_string(),
+        };
+        let level_style = level_style(level);
+        let width = self
+            .labels
+            .iter()
+            .map(|label| line_cols(&label.error).0 .0)
+            .chain([self.line_cols().0 .0])
+            .max()
+            .unwrap_or_default()
+            .to_string()
+            .len();
+        let gutter = paint(BLUE, &format!("{} |", " ".repeat(width)));
+
+        let mut out = Self::paint_header(&format!("{level}: {}", self.message), color) + "\n";
+        let arrow = paint(BLUE, &format!("{}-->", " ".repeat(width)));
+        out += &format!("{arrow} {}\n", location(&self.file, &self.error));
+        out += &format!("{gutter}\n");
+        out += &snippet(&self.error, width, &paint, '^', level_style, "");
+
+        for label in self.labels.iter() {
+            out += &format!("{gutter}\n");
+            if label.file != self.file {
+                let arrow = paint(BLUE, &format!("{}:::", " ".repeat(width)));
+                out += &format!("{arrow} {}\n", location(&label.file, &label.error));
+                out += &format!("{gutter}\n");
+            }
+            out += &snippet(&label.error, width, &paint, '-', BLUE, &label.message);
+        }
+
+        if !self.notes.is_empty() {
+            out += &format!("{gutter}\n");
+        }
+        for note in self.notes.iter() {
+            let equals = paint(BLUE, &format!("{} =", " ".repeat(width)));
+            out += &format!("{equals} {}: {note}\n", paint(BOLD, "note"));
+        }
+        out.trim_end().to_string()
+    }
+}
+
+fn level_style(level: &str) -> &'static str {
+    match level.starts_with("warning") {
+        true => YELLOW,
+        false => RED,
+    }
+}
+
+fn line_cols(error: &RuleError) -> ((usize, usize), (usize, usize)) {
+    match error.line_col {
+        LineColLocation::Pos(pos) => (pos, pos),
+        LineColLocation::Span(start, end) => (start, end),
+    }
+}
+
+fn location(file: &str, error: &RuleError) -> String {
+    let ((line, col), _) = line_cols(error);
+    match file.is_empty() {
+        true => format!("{line}:{col}"),
+        false => format!("{file}:{line}:{col}"),
+    }
+}
+
+/// The line where an error starts, with markers under the part of it the error points
+/// to. Errors that span several lines are marked until the end of the first one.
+fn snippet(
+    error: &RuleError,
+    width: usize,
+    paint: &dyn Fn(&str, &str) -> String,
+    marker: char,
+    style: &str,
+    message: &str,
+) -> String {
+    let ((line, col), (end_line, end_col)) = line_cols(error);
+    // pest marks the line breaks that are part of the span
+    let text = error.line().trim_end_matches(['␊', '\r', '\n']);
+    let chars: Vec<char> = text.chars().collect();
+    let start = (col - 1).min(chars.len());
+    let end = match end_line == line {
+        true => (end_col - 1).min(chars.len()),
+        false => chars.len(),
+    };
+    // tabs are kept so that the markers line up with the code
+    let padding: String = chars[..start]
+        .iter()
+        .map(|c| if *c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let markers = marker.to_string().repeat((end - start).max(1));
+    let number = paint(BLUE, &format!("{line:>width$} |"));
+    let gutter = paint(BLUE, &format!("{} |", " ".repeat(width)));
+    let mut marked = format!("{markers} {message}");
+    marked.truncate(marked.trim_end().len());
+    format!(
+        "{number} {text}\n{gutter} {padding}{}\n",
+        paint(style, &marked)
+    )
+}
+
+impl Display for LocatedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render("error", false))
+    }
+}
+
+impl Error for LocatedError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::OwnedSpan;
+    use pest::Span;
+
+    const INPUT: &str = "type User {\n    id: ID!\n}\n\ntype User {\n    name: String\n}\n";
+
+    fn span(start: usize, end: usize, file: &str) -> OwnedSpan {
+        OwnedSpan::from(Span::new(INPUT, start, end).unwrap(), file)
+    }
+
+    #[test]
+    fn test_renders_the_code_with_labels_and_notes() {
+        let err = span(32, 36, "main.graphqxl")
+            .make_error("type \"User\" is already defined")
+            .with_label(span(5, 9, "main.graphqxl").make_label("first defined here"))
+            .with_label(span(5, 9, "user.graphqxl").make_label("also defined here"))
+            .with_note("types can only be defined once");
+        assert_eq!(
+            err.render("error", false),
+            r#"error: type "User" is already defined
+ --> main.graphqxl:5:6
+  |
+5 | type User {
+  |      ^^^^
+  |
+1 | type User {
+  |      ---- first defined here
+  |
+ ::: user.graphqxl:1:6
+  |
+1 | type User {
+  |      ---- also defined here
+  |
+  = note: types can only be defined once"#
+        );
+    }
+
+    #[test]
+    fn test_renders_the_code_with_colors() {
+        let err = span(16, 18, "main.graphqxl").make_error("unknown type");
+        assert_eq!(
+            err.render("warning", true),
+            "\x1b[1;33mwarning\x1b[0m\x1b[1m: unknown type\x1b[0m\n\
+             \x1b[1;34m -->\x1b[0m main.graphqxl:2:5\n\
+             \x1b[1;34m  |\x1b[0m\n\
+             \x1b[1;34m2 |\x1b[0m     id: ID!\n\
+             \x1b[1;34m  |\x1b[0m     \x1b[1;33m^^\x1b[0m"
+        );
+    }
+}
@@ -1,11 +1,13 @@
-mod already_defined_error;
 mod custom_error;
+mod error_list;
+mod located_error;
 mod owned_span;
 mod parse_full_input;
 pub(crate) mod unknown_rule_error;
 
-pub(crate) use already_defined_error::*;
 pub(crate) use custom_error::*;
+pub use error_list::*;
+pub use located_error::*;
 pub use owned_span::*;
 pub(crate) use unknown_rule_error::*;
 
@@ -1,6 +1,8 @@
+use pest::error::{InputLocation, LineColLocation};
 use pest::Span;
 
 use crate::parser::RuleError;
+use crate::utils::{Label, LocatedError};
 
 #[derive(Clone, Debug)]
 pub struct OwnedSpan {
@@ -34,12 +36,63 @@ impl Default for OwnedSpan {
 }
 
 impl OwnedSpan {
-    pub fn make_error(&self, msg: &str) -> Box<RuleError> {
+    fn with_message(&self, msg: &str) -> RuleError {
         let mut err = self.err_placeholder.clone();
         err.variant = pest::error::ErrorVariant::CustomError {
-            message: format!("{}:{} {}", self.file, self.line, msg),
+            message: msg.to_string(),
         };
-        Box::new(err)
+        err
+    }
+
+    pub fn make_error(&self, msg: &str) -> Box<LocatedError> {
+        Box::new(LocatedError {
+            file: self.file.clone(),
+            message: msg.to_string(),
+            error: self.with_message(msg),
+            labels: vec![],
+            notes: vec![],
+        })
+    }
+
+    /// The part of this span between the `start` and `end` bytes of its text, as long as
+    /// it is in its first line.
+    pub fn slice(&self, start: usize, end: usize) -> Option<OwnedSpan> {
+        let text = self.input.get(..end)?;
+        if start > end || text.contains('\n') || !self.input.is_char_boundary(start) {
+            return None;
+        }
+        let col = self.col + text[..start].chars().count();
+        let end_col = self.col + text.chars().count();
+        let mut err = self.err_placeholder.clone();
+        err.location = InputLocation::Span((self.start + start, self.start + end));
+        err.line_col = LineColLocation::Span((self.line, col), (self.line, end_col));
+        Some(Self {
+            err_placeholder: err,
+            file: self.file.clone(),
+            line: self.line,
+            col,
+            input: text[start..].to_string(),
+            start: self.start + start,
+            end: self.start + end,
+        })
+    }
+
+    /// This span without the whitespace and the commas it ends with, which are ignored
+    /// by the grammar but end up in the spans of the rules that have optional endings.
+    pub fn trim_end(&self) -> OwnedSpan {
+        let trimmed = self
+            .input
+            .trim_end_matches(|c: char| c.is_whitespace() || c == ',');
+        self.slice(0, trimmed.len()).unwrap_or_else(|| self.clone())
+    }
+
+    /// Points to this span from an error raised somewhere else.
+    pub fn make_label(&self, msg: &str) -> Label {
+        Label {
+            file: self.file.clone(),
+            message: msg.to_string(),
+            error: self.with_message(msg),
+        }
     }
 }
 
@@ -70,3 +123,33 @@ impl<'a> OwnedSpan {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slices_the_first_line() {
+        let input = "type Foo {\n    bar: Baz\n}";
+        let span = OwnedSpan::from(Span::new(input, 11, 23).unwrap(), "foo.graphqxl");
+        let sliced = span.slice(9, 12).unwrap();
+        assert_eq!(sliced.input, "Baz");
+        assert_eq!((sliced.line, sliced.col), (2, 10));
+        assert_eq!((sliced.start, sliced.end), (20, 23));
+        let err = sliced.make_error("Baz is undefined");
+        assert_eq!(err.line_cols(), ((2, 10), (2, 13)));
+        assert_eq!(err.offsets(), (20, 23));
+        let block = OwnedSpan::from(Span::new(input, 0, input.len()).unwrap(), "foo.graphqxl");
+        assert!(block.slice(5, 8).is_some());
+        assert!(block.slice(5, 20).is_none());
+    }
+
+    #[test]
+    fn test_trims_the_end() {
+        let input = "foo(bar: Int,  baz: Int)";
+        let span = OwnedSpan::from(Span::new(input, 4, 15).unwrap(), "foo.graphqxl");
+        let trimmed = span.trim_end();
+        assert_eq!(trimmed.input, "bar: Int");
+        assert_eq!(trimmed.make_error("").offsets(), (4, 12));
+    }
+}
@@ -0,0 +1,359 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+pub(crate) const GRAPHQXL_EXTENSION: &str = ".graphqxl";
+const GRAPHQL_EXTENSION: &str = ".graphql";
+const MODULES_DIRS: [&str; 2] = ["graphqxl_modules", "node_modules"];
+
+/// Adds the `.graphqxl` extension to an import, unless it already has it or it refers
+/// to a plain `.graphql` file.
+pub(crate) fn with_graphqxl_extension(file_name: &str) -> String {
+    if file_name.ends_with(GRAPHQXL_EXTENSION) || is_plain_graphql(file_name) {
+        file_name.to_string()
+    } else {
+        file_name.to_string() + GRAPHQXL_EXTENSION
+    }
+}
+
+/// Plain `.graphql` files only contain standard GraphQL definitions.
+pub(crate) fn is_plain_graphql(file_name: &str) -> bool {
+    file_name.ends_with(GRAPHQL_EXTENSION)
+}
+
+/// Imports starting with `./` or `../`, or absolute ones, are only looked up
+/// relative to the importing file.
+pub(crate) fn is_relative_import(file_name: &str) -> bool {
+    file_name.starts_with("./")
+        || file_name.starts_with("../")
+        || Path::new(file_name).is_absolute()
+}
+
+/// An import that refers to all the files of a directory, like `./entities/*`,
+/// or to all the files inside it and its subdirectories, like `./entities/**`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ImportGlob<'a> {
+    pub(crate) dir: &'a str,
+    pub(crate) recursive: bool,
+}
+
+/// Reads the glob of an import, if it has one, failing if it uses wildcards
+/// anywhere else than in its last segment.
+pub(crate) fn parse_import_glob(file_name: &str) -> Result<Option<ImportGlob<'_>>, String> {
+    if !file_name.contains('*') {
+        return Ok(None);
+    }
+    let (dir, last) = match file_name.rsplit_once('/') {
+        Some((dir, last)) => (dir, last),
+        None => (".", file_name),
+    };
+    if dir.contains('*') || (last != "*" && last != "**") {
+        return Err(format!(
+            "invalid glob \"{file_name}\", only \"<dir>/*\" and \"<dir>/**\" are supported"
+        ));
+    }
+    Ok(Some(ImportGlob {
+        dir,
+        recursive: last == "**",
+    }))
+}
+
+/// Trait for a virtual file system.
+pub trait VirtualFileSystem {
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>>;
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>>;
+    fn exists(&self, path: &Path) -> bool;
+    /// Lists the files inside a directory, and inside its subdirectories if `recursive`.
+    /// File systems that do not implement it cannot resolve glob imports.
+    fn list_files(&self, dir: &Path, _recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        Err(Box::new(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "cannot list the files in {}, this file system does not support it",
+                dir.display()
+            ),
+        )))
+    }
+
+    /// Finds the file referenced by an import made from a file placed in `dir`. The import
+    /// is looked up relative to `dir`, then in each one of the `roots`, and then in the
+    /// `graphqxl_modules` and `node_modules` directories of `dir` and of its ancestors.
+    /// In each place, both `<import>.graphqxl` and `<import>/index.graphqxl` are tried.
+    fn resolve_import(&self, dir: &Path, file_name: &str, roots: &[PathBuf]) -> Option<PathBuf> {
+        let find_in = |base: &Path| {
+            [
+                base.join(with_graphqxl_extension(file_name)),
+                base.join(file_name).join(with_graphqxl_extension("index")),
+            ]
+            .into_iter()
+            .find(|candidate| self.exists(candidate))
+        };
+        if let Some(found) = find_in(dir) {
+            return Some(found);
+        }
+        if is_relative_import(file_name) {
+            return None;
+        }
+        for root in roots.iter() {
+            if let Some(found) = find_in(root) {
+                return Some(found);
+            }
+        }
+        for ancestor in dir.ancestors() {
+            for modules_dir in MODULES_DIRS {
+                if let Some(found) = find_in(&ancestor.join(modules_dir)) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The file system of the machine where the code is running.
+pub struct NativeFileSystem;
+
+impl VirtualFileSystem for NativeFileSystem {
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+        Ok(fs::canonicalize(path)?)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn list_files(&self, dir: &Path, recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let mut files = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if recursive {
+                    files.extend(self.list_files(&path, recursive)?);
+                }
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// A file system kept in memory, where paths are used as they are, without resolving
+/// them. Files can be added or changed after it is created.
+#[derive(Default)]
+pub struct MemoryFileSystem {
+    files: RefCell<HashMap<PathBuf, String>>,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, or replaces its content if it already exists.
+    pub fn insert<P: Into<PathBuf>>(&self, path: P, content: &str) {
+        self.files
+            .borrow_mut()
+            .insert(path.into(), content.to_string());
+    }
+}
+
+impl<P: Into<PathBuf>, C: Into<String>> FromIterator<(P, C)> for MemoryFileSystem {
+    fn from_iter<I: IntoIterator<Item = (P, C)>>(files: I) -> Self {
+        let files = files
+            .into_iter()
+            .map(|(path, content)| (path.into(), content.into()))
+            .collect();
+        Self {
+            files: RefCell::new(files),
+        }
+    }
+}
+
+impl<P: Into<PathBuf>, C: Into<String>, const N: usize> From<[(P, C); N]> for MemoryFileSystem {
+    fn from(files: [(P, C); N]) -> Self {
+        files.into_iter().collect()
+    }
+}
+
+impl VirtualFileSystem for MemoryFileSystem {
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+        match self.files.borrow().get(path) {
+            Some(content) => Ok(content.clone()),
+            None => Err(Box::new(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} does not exist", path.display()),
+            ))),
+        }
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+        Ok(path.to_path_buf())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.borrow().contains_key(path)
+    }
+
+    fn list_files(&self, dir: &Path, recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let mut files: Vec<PathBuf> = self
+            .files
+            .borrow()
+            .keys()
+            .filter(|path| match path.strip_prefix(dir) {
+                Ok(relative) => recursive || relative.components().count() == 1,
+                Err(_) => false,
+            })
+            .cloned()
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vfs(files: &[&str]) -> MemoryFileSystem {
+        files.iter().map(|file| (*file, "")).collect()
+    }
+
+    /// Implements only what every file system has to.
+    struct MinimalFileSystem;
+
+    impl VirtualFileSystem for MinimalFileSystem {
+        fn read_to_string(&self, _: &Path) -> Result<String, Box<dyn Error>> {
+            Ok("".to_string())
+        }
+
+        fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+            Ok(path.to_path_buf())
+        }
+
+        fn exists(&self, _: &Path) -> bool {
+            false
+        }
+    }
+
+    fn resolve(vfs: &MemoryFileSystem, file_name: &str, roots: &[&str]) -> Option<PathBuf> {
+        let roots: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+        vfs.resolve_import(Path::new("/repo/service/schema"), file_name, &roots)
+    }
+
+    #[test]
+    fn test_prefers_files_relative_to_the_importing_one() {
+        let vfs = vfs(&[
+            "/repo/service/schema/common.graphqxl",
+            "/repo/shared/common.graphqxl",
+        ]);
+        assert_eq!(
+            resolve(&vfs, "common", &["/repo/shared"]),
+            Some(PathBuf::from("/repo/service/schema/common.graphqxl"))
+        );
+    }
+
+    #[test]
+    fn test_looks_in_roots_in_order() {
+        let vfs = vfs(&[
+            "/repo/shared/@shared/pagination.graphqxl",
+            "/repo/other/@shared/pagination.graphqxl",
+        ]);
+        assert_eq!(
+            resolve(&vfs, "@shared/pagination", &["/repo/other", "/repo/shared"]),
+            Some(PathBuf::from("/repo/other/@shared/pagination.graphqxl"))
+        );
+    }
+
+    #[test]
+    fn test_looks_in_modules_dirs_of_ancestors() {
+        let vfs = vfs(&[
+            "/repo/node_modules/@shared/pagination.graphqxl",
+            "/repo/service/graphqxl_modules/@shared/money/index.graphqxl",
+        ]);
+        assert_eq!(
+            resolve(&vfs, "@shared/pagination", &[]),
+            Some(PathBuf::from(
+                "/repo/node_modules/@shared/pagination.graphqxl"
+            ))
+        );
+        assert_eq!(
+            resolve(&vfs, "@shared/money", &[]),
+            Some(PathBuf::from(
+                "/repo/service/graphqxl_modules/@shared/money/index.graphqxl"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_keeps_graphql_extension() {
+        let vfs = vfs(&["/repo/node_modules/federation/spec.graphql"]);
+        assert_eq!(
+            resolve(&vfs, "federation/spec.graphql", &[]),
+            Some(PathBuf::from("/repo/node_modules/federation/spec.graphql"))
+        );
+    }
+
+    #[test]
+    fn test_does_not_look_elsewhere_for_relative_imports() {
+        let vfs = vfs(&["/repo/shared/common.graphqxl"]);
+        assert_eq!(resolve(&vfs, "./common", &["/repo/shared"]), None);
+    }
+
+    #[test]
+    fn test_does_not_list_files_by_default() {
+        let err = MinimalFileSystem
+            .list_files(Path::new("/repo/shared"), false)
+            .unwrap_err();
+        assert!(err.to_string().contains("does not support it"));
+    }
+
+    #[test]
+    fn test_lists_files_in_memory() {
+        let vfs = vfs(&[
+            "/repo/shared/b.graphqxl",
+            "/repo/shared/a.graphqxl",
+            "/repo/shared/nested/c.graphqxl",
+            "/repo/other.graphqxl",
+        ]);
+        let list = |recursive| {
+            vfs.list_files(Path::new("/repo/shared"), recursive)
+                .unwrap()
+        };
+        assert_eq!(
+            list(false),
+            vec![
+                PathBuf::from("/repo/shared/a.graphqxl"),
+                PathBuf::from("/repo/shared/b.graphqxl"),
+            ]
+        );
+        assert_eq!(list(true).len(), 3);
+    }
+
+    #[test]
+    fn test_parses_import_globs() {
+        assert_eq!(parse_import_glob("./entities/user"), Ok(None));
+        assert_eq!(
+            parse_import_glob("./entities/*"),
+            Ok(Some(ImportGlob {
+                dir: "./entities",
+                recursive: false
+            }))
+        );
+        assert_eq!(
+            parse_import_glob("entities/**"),
+            Ok(Some(ImportGlob {
+                dir: "entities",
+                recursive: true
+            }))
+        );
+        parse_import_glob("./entities/*.graphqxl").unwrap_err();
+        parse_import_glob("./*/user").unwrap_err();
+    }
+}
@@ -1,6 +1,6 @@
 use crate::synth_spec::SpecSynth;
-pub use crate::synths::SourceMapEntry;
 pub use crate::synths::SynthConfig;
+pub use crate::synths::{SourceMap, SourceMapEntry};
 use crate::synths::{Synth, SynthContext};
 use graphqxl_parser::Spec;
 
@@ -21,7 +21,7 @@ mod synth_value_type;
 mod synths;
 mod utils;
 
-pub fn synth_spec(spec: Spec, config: SynthConfig) -> (String, Vec<SourceMapEntry>) {
+pub fn synth_spec(spec: Spec, config: SynthConfig) -> (String, SourceMap) {
     let mut context = SynthContext {
         source_map: vec![],
         result: "".to_string(),
@@ -32,5 +32,5 @@ pub fn synth_spec(spec: Spec, config: SynthConfig) -> (String, Vec<SourceMapEntr
         config,
     };
     SpecSynth(spec).synth(&mut context);
-    (context.result, context.source_map)
+    (context.result, SourceMap::new(context.source_map))
 }
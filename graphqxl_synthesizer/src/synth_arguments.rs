@@ -4,7 +4,8 @@ use crate::synth_identifier::IdentifierSynth;
 use crate::synth_value_data::ValueDataSynth;
 use crate::synth_value_type::ValueTypeSynth;
 use crate::synths::{
-    ChainSynth, MultilineListSynth, OneLineListSynth, PairSynth, StringSynth, Synth, SynthContext,
+    ChainSynth, MultilineListSynth, OneLineListSynth, PairSynth, SourceSynth, StringSynth, Synth,
+    SynthContext,
 };
 use graphqxl_parser::{Argument, ArgumentDefaultValue};
 
@@ -18,7 +19,8 @@ impl Synth for ArgumentsSynth {
             .iter()
             .map(|argument| {
                 let mut v: Vec<Box<dyn Synth>> = vec![
-                    Box::new(StringSynth(argument.name.id.clone() + ": ")),
+                    Box::new(IdentifierSynth(argument.name.clone())),
+                    Box::new(StringSynth::from(": ")),
                     Box::new(ValueTypeSynth(argument.value_type.clone())),
                 ];
                 if let ArgumentDefaultValue::ValueData(default) = &argument.default {
@@ -36,13 +38,14 @@ impl Synth for ArgumentsSynth {
 
                 if !argument.description.is_empty() {
                     at_least_one_description = true;
+                    // the span of the argument starts at its description
                     Box::new(PairSynth {
                         first: DescriptionSynth::text(&argument.description),
-                        last: ChainSynth(v),
+                        last: SourceSynth(ChainSynth(v), argument.name.span.clone()),
                         line_jump_sep: true,
                     })
                 } else {
-                    Box::new(ChainSynth(v)) as Box<dyn Synth>
+                    Box::new(SourceSynth(ChainSynth(v), argument.span.clone())) as Box<dyn Synth>
                 }
             })
             .collect();
@@ -28,7 +28,7 @@ impl Synth for BlockDefSynth {
         v.push(Box::new(StringSynth::from(" ")));
         v.push(Box::new(IdentifierSynth(self.0.name.clone())));
         if let Some(implements) = &self.0.implements {
-            let first = implements.interfaces.get(0).unwrap();
+            let first = implements.interfaces.first().unwrap();
             v.push(Box::new(StringSynth::from(" implements ")));
             v.push(Box::new(IdentifierSynth(first.clone())));
             for i in 1..implements.interfaces.len() {
@@ -44,10 +44,11 @@ impl Synth for BlockDefSynth {
         let mut inner_synths = Vec::new();
         for entry in self.0.entries.iter() {
             if let BlockEntry::Field(block_field) = entry {
-                inner_synths.push(BlockFieldSynth(block_field.clone()));
+                inner_synths.push(BlockFieldSynth(block_field.as_ref().clone()));
             }
         }
-        if !(inner_synths.is_empty() && self.0.extend) {
+        // definitions without fields, which are only allowed in plain GraphQL, have no body
+        if !inner_synths.is_empty() {
             v.push(Box::new(StringSynth::from(" ")));
             v.push(Box::new(MultilineListSynth::no_suffix((
                 "{",
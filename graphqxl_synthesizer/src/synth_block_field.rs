@@ -2,33 +2,47 @@ use crate::synth_arguments::ArgumentsSynth;
 use crate::synth_description::DescriptionSynth;
 use crate::synth_directive::DirectiveSynth;
 use crate::synth_identifier::IdentifierSynth;
+use crate::synth_value_data::ValueDataSynth;
 use crate::synth_value_type::ValueTypeSynth;
-use crate::synths::{ChainSynth, PairSynth, StringSynth, Synth, SynthContext};
+use crate::synths::{ChainSynth, PairSynth, SourceSynth, StringSynth, Synth, SynthContext};
 use graphqxl_parser::BlockField;
 
 pub(crate) struct BlockFieldSynth(pub(crate) BlockField);
 
 impl Synth for BlockFieldSynth {
     fn synth(&self, context: &mut SynthContext) -> bool {
+        // the description is written apart, so the span of a field that has one, which
+        // starts at it, is not used
+        let span = match self.0.description.is_empty() {
+            true => &self.0.span,
+            false => &self.0.name.span,
+        };
         let synth = PairSynth {
             line_jump_sep: true,
             first: DescriptionSynth::text(&self.0.description),
-            last: ChainSynth({
-                let mut v: Vec<Box<dyn Synth>> =
-                    vec![Box::new(IdentifierSynth(self.0.name.clone()))];
-                if !self.0.args.is_empty() {
-                    v.push(Box::new(ArgumentsSynth(self.0.args.clone())));
-                }
-                if let Some(value_type) = &self.0.value_type {
-                    v.push(Box::new(StringSynth::from(": ")));
-                    v.push(Box::new(ValueTypeSynth(value_type.clone())));
-                }
-                for directive in self.0.directives.iter() {
-                    v.push(Box::new(StringSynth::from(" ")));
-                    v.push(Box::new(DirectiveSynth(directive.clone())));
-                }
-                v
-            }),
+            last: SourceSynth(
+                ChainSynth({
+                    let mut v: Vec<Box<dyn Synth>> =
+                        vec![Box::new(IdentifierSynth(self.0.name.clone()))];
+                    if !self.0.args.is_empty() {
+                        v.push(Box::new(ArgumentsSynth(self.0.args.clone())));
+                    }
+                    if let Some(value_type) = &self.0.value_type {
+                        v.push(Box::new(StringSynth::from(": ")));
+                        v.push(Box::new(ValueTypeSynth(value_type.clone())));
+                    }
+                    if let Some(default) = &self.0.default {
+                        v.push(Box::new(StringSynth::from(" = ")));
+                        v.push(Box::new(ValueDataSynth(default.clone())));
+                    }
+                    for directive in self.0.directives.iter() {
+                        v.push(Box::new(StringSynth::from(" ")));
+                        v.push(Box::new(DirectiveSynth(directive.clone())));
+                    }
+                    v
+                }),
+                span.clone(),
+            ),
         };
         synth.synth(context)
     }
@@ -37,7 +51,7 @@ impl Synth for BlockFieldSynth {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use graphqxl_parser::{Argument, Directive, ValueType};
+    use graphqxl_parser::{Argument, Directive, ValueData, ValueType};
 
     #[test]
     fn test_no_description_no_args_no_type() {
@@ -76,6 +90,17 @@ field"
         assert_eq!(synth.synth_zero(), "\"my description\"\nfield: String");
     }
 
+    #[test]
+    fn test_type_default_value() {
+        let synth = BlockFieldSynth(
+            BlockField::build("field")
+                .int()
+                .default(ValueData::int(-1))
+                .directive(Directive::build("dir")),
+        );
+        assert_eq!(synth.synth_zero(), "field: Int = -1 @dir");
+    }
+
     #[test]
     fn test_description_args_type() {
         let synth = BlockFieldSynth(
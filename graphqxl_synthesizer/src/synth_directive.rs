@@ -1,6 +1,6 @@
 use crate::synth_function_call::FunctionCallSynth;
 use crate::synth_identifier::IdentifierSynth;
-use crate::synths::{ChainSynth, StringSynth};
+use crate::synths::{ChainSynth, SourceSynth, StringSynth};
 use crate::{Synth, SynthContext};
 use graphqxl_parser::Directive;
 
@@ -16,7 +16,7 @@ impl Synth for DirectiveSynth {
             v.push(Box::new(FunctionCallSynth(call.clone())));
         }
 
-        let synth = ChainSynth(v);
+        let synth = SourceSynth(ChainSynth(v), self.0.span.clone());
         synth.synth(context)
     }
 }
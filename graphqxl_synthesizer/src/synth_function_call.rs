@@ -1,6 +1,6 @@
 use crate::synth_identifier::IdentifierSynth;
 use crate::synth_value_data::ValueDataSynth;
-use crate::synths::{ChainSynth, MultilineListSynth, OneLineListSynth, StringSynth};
+use crate::synths::{ChainSynth, MultilineListSynth, OneLineListSynth, SourceSynth, StringSynth};
 use crate::{Synth, SynthContext};
 use graphqxl_parser::FunctionCall;
 
@@ -13,11 +13,12 @@ impl Synth for FunctionCallSynth {
             .inputs
             .iter()
             .map(|e| {
-                ChainSynth(vec![
+                let synth = ChainSynth(vec![
                     Box::new(IdentifierSynth(e.name.clone())),
                     Box::new(StringSynth::from(": ")),
                     Box::new(ValueDataSynth(e.value.clone())),
-                ])
+                ]);
+                SourceSynth(synth, e.span.clone())
             })
             .collect();
         if self.0.inputs.len() > context.config.max_one_line_args {
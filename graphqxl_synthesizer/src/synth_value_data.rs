@@ -32,6 +32,14 @@ impl Synth for ValueDataSynth {
                     context.write(&format!("\"{}\"", escape_non_escaped_quotes(v)));
                     true
                 }
+                ValueBasicData::Null => {
+                    context.write("null");
+                    true
+                }
+                ValueBasicData::Enum(v) => {
+                    context.write(v);
+                    true
+                }
             },
             ValueData::List(items) => {
                 context.write("[");
@@ -6,40 +6,51 @@ pub(crate) struct ValueTypeSynth(pub(crate) ValueType);
 
 impl Synth for ValueTypeSynth {
     fn synth(&self, context: &mut SynthContext) -> bool {
-        match &self.0 {
-            ValueType::Basic(basic, span) => match &basic {
-                ValueBasicType::Int => {
-                    context.write_with_source("Int", span);
-                    true
-                }
-                ValueBasicType::Float => {
-                    context.write_with_source("Float", span);
-                    true
-                }
-                ValueBasicType::String => {
-                    context.write_with_source("String", span);
-                    true
-                }
-                ValueBasicType::Boolean => {
-                    context.write_with_source("Boolean", span);
-                    true
-                }
-                ValueBasicType::Object(name) => {
-                    IdentifierSynth(name.clone()).synth(context);
-                    true
-                }
-            },
-            ValueType::NonNullable(value_type, span) => {
-                ValueTypeSynth(*value_type.clone()).synth(context);
-                context.write_with_source("!", span);
+        let position = context.position();
+        let written = synth_value_type(&self.0, context);
+        context.map_value_type(position, self.0.span());
+        written
+    }
+}
+
+fn synth_value_type(value_type: &ValueType, context: &mut SynthContext) -> bool {
+    match value_type {
+        ValueType::Basic(basic, span) => match &basic {
+            ValueBasicType::Int => {
+                context.write_with_source("Int", span);
+                true
+            }
+            ValueBasicType::Float => {
+                context.write_with_source("Float", span);
+                true
+            }
+            ValueBasicType::String => {
+                context.write_with_source("String", span);
+                true
+            }
+            ValueBasicType::Boolean => {
+                context.write_with_source("Boolean", span);
                 true
             }
-            ValueType::Array(value_type, span) => {
-                context.write_with_source("[", span);
-                ValueTypeSynth(*value_type.clone()).synth(context);
-                context.write_with_source("]", span);
+            ValueBasicType::Object(name) => {
+                IdentifierSynth(name.clone()).synth(context);
                 true
             }
+            ValueBasicType::GenericObject(_) => {
+                context.write_with_source(&basic.to_string(), span);
+                true
+            }
+        },
+        ValueType::NonNullable(value_type, span) => {
+            synth_value_type(value_type, context);
+            context.write_with_source("!", span);
+            true
+        }
+        ValueType::Array(value_type, span) => {
+            context.write_with_source("[", span);
+            synth_value_type(value_type, context);
+            context.write_with_source("]", span);
+            true
         }
     }
 }
@@ -2,6 +2,8 @@ mod chain_synth;
 mod multiline_list_synth;
 mod one_line_list_synth;
 mod pair_synth;
+mod source_map;
+mod source_synth;
 mod string_synth;
 mod synth_context;
 
@@ -9,6 +11,8 @@ pub(crate) use chain_synth::*;
 pub(crate) use multiline_list_synth::*;
 pub(crate) use one_line_list_synth::*;
 pub(crate) use pair_synth::*;
+pub use source_map::{SourceMap, SourceMapEntry};
+pub(crate) use source_synth::*;
 pub(crate) use string_synth::*;
-pub use synth_context::{SynthConfig, SourceMapEntry};
+pub use synth_context::SynthConfig;
 pub(crate) use synth_context::*;
@@ -0,0 +1,167 @@
+use graphqxl_parser::OwnedSpan;
+use std::cmp::Reverse;
+
+/// A piece of the generated schema, between the `start` and `stop` bytes, that comes
+/// from `span`. `verbatim` tells if it was written exactly as it is in the source, and
+/// `value_type` if it is the whole value type of a field or an argument.
+pub struct SourceMapEntry {
+    pub line: usize,
+    pub col: usize,
+    pub start: usize,
+    pub stop: usize,
+    pub span: OwnedSpan,
+    pub verbatim: bool,
+    pub value_type: bool,
+}
+
+/// The pieces of the generated schema that come from the source, sorted so that the
+/// tightest one around any part of the schema can be found. Pieces are nested inside
+/// others, like a field name inside its field.
+#[derive(Default)]
+pub struct SourceMap {
+    entries: Vec<SourceMapEntry>,
+}
+
+impl SourceMap {
+    pub(crate) fn new(mut entries: Vec<SourceMapEntry>) -> Self {
+        // the ones that start at the same place go from the widest to the tightest
+        entries.sort_by_key(|entry| (entry.start, Reverse(entry.stop)));
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[SourceMapEntry] {
+        &self.entries
+    }
+
+    /// The tightest entry around the generated text between `start` and `end`. If there
+    /// is none, the first one inside that text is taken.
+    pub fn lookup(&self, start: usize, end: usize) -> Option<&SourceMapEntry> {
+        // the ones that start later cannot contain the text
+        let candidates = self.entries.partition_point(|entry| entry.start <= start);
+        let around = self.entries[..candidates]
+            .iter()
+            .rev()
+            .find(|entry| end <= entry.stop);
+        if around.is_some() {
+            return around;
+        }
+        self.entries[candidates..]
+            .iter()
+            .take_while(|entry| entry.start < end)
+            .find(|entry| entry.stop <= end)
+    }
+
+    /// Where the generated text between `start` and `end` comes from. When it is in an
+    /// entry that was written as it is in the source, it points to the exact columns.
+    pub fn locate(&self, start: usize, end: usize) -> Option<OwnedSpan> {
+        let entry = self.lookup(start, end)?;
+        if entry.verbatim && entry.start <= start && end <= entry.stop {
+            if let Some(span) = entry.span.slice(start - entry.start, end - entry.start) {
+                return Some(span);
+            }
+        }
+        Some(entry.span.clone())
+    }
+
+    /// Where the value type of the field or argument written between `start` and `end`
+    /// comes from. The arguments of a field are written before its value type, so the
+    /// last one inside that text is taken. If there is none, it is like [Self::locate].
+    pub fn locate_value_type(&self, start: usize, end: usize) -> Option<OwnedSpan> {
+        let first = self.entries.partition_point(|entry| entry.start < start);
+        let value_type = self.entries[first..]
+            .iter()
+            .take_while(|entry| entry.start < end)
+            .filter(|entry| entry.value_type && entry.stop <= end)
+            .last();
+        match value_type {
+            Some(entry) => Some(entry.span.clone()),
+            None => self.locate(start, end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pest::Span;
+
+    const INPUT: &str = "type Foo {\n  bar(baz: Int): String\n}";
+
+    fn entry(start: usize, stop: usize, span: (usize, usize), verbatim: bool) -> SourceMapEntry {
+        let span = Span::new(INPUT, span.0, span.1).unwrap();
+        SourceMapEntry {
+            line: 0,
+            col: 0,
+            start,
+            stop,
+            span: OwnedSpan::from(span, "foo.graphqxl"),
+            verbatim,
+            value_type: false,
+        }
+    }
+
+    fn value_type(start: usize, stop: usize) -> SourceMapEntry {
+        SourceMapEntry {
+            value_type: true,
+            ..entry(start, stop, (start, stop), true)
+        }
+    }
+
+    fn source_map() -> SourceMap {
+        SourceMap::new(vec![
+            entry(5, 8, (5, 8), true),
+            entry(13, 16, (13, 16), true),
+            entry(17, 20, (17, 20), true),
+            entry(22, 25, (22, 25), true),
+            value_type(22, 25),
+            entry(17, 25, (17, 25), true),
+            entry(28, 34, (28, 34), true),
+            value_type(28, 34),
+            entry(13, 34, (13, 16), false),
+        ])
+    }
+
+    #[test]
+    fn test_looks_up_the_tightest_entry() {
+        let source_map = source_map();
+        assert_eq!(source_map.lookup(22, 25).unwrap().span.input, "Int");
+        assert_eq!(source_map.lookup(17, 25).unwrap().span.input, "baz: Int");
+        assert_eq!(source_map.lookup(18, 19).unwrap().span.input, "baz");
+        assert_eq!(source_map.lookup(13, 34).unwrap().start, 13);
+        assert_eq!(source_map.lookup(13, 34).unwrap().stop, 34);
+        assert_eq!(source_map.lookup(20, 27).unwrap().span.input, "bar");
+    }
+
+    #[test]
+    fn test_looks_up_the_first_entry_inside_the_text() {
+        let source_map = source_map();
+        assert_eq!(source_map.lookup(0, 36).unwrap().span.input, "Foo");
+        assert_eq!(source_map.lookup(9, 12).map(|e| e.start), None);
+    }
+
+    #[test]
+    fn test_locates_the_exact_columns() {
+        let source_map = source_map();
+        let span = source_map.locate(22, 25).unwrap();
+        assert_eq!((span.line, span.col), (2, 12));
+        let span = source_map.locate(23, 25).unwrap();
+        assert_eq!(span.input, "nt");
+        assert_eq!((span.line, span.col), (2, 13));
+        // entries that were not written as they are only point to their span
+        let span = source_map.locate(26, 27).unwrap();
+        assert_eq!(span.input, "bar");
+    }
+
+    #[test]
+    fn test_locates_the_value_type_of_a_field() {
+        let source_map = source_map();
+        let span = source_map.locate_value_type(13, 34).unwrap();
+        assert_eq!(span.input, "String");
+        assert_eq!((span.line, span.col), (2, 18));
+        let span = source_map.locate_value_type(17, 25).unwrap();
+        assert_eq!(span.input, "Int");
+        // without a value type inside, the text is located as usual
+        let span = source_map.locate_value_type(18, 19).unwrap();
+        assert_eq!(span.input, "a");
+    }
+}
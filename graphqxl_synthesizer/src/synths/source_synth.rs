@@ -0,0 +1,15 @@
+use crate::synths::{Synth, SynthContext};
+use graphqxl_parser::OwnedSpan;
+
+/// Maps everything written by the inner synth to `span`, so that the problems found in
+/// any part of it can be traced back to the source.
+pub(crate) struct SourceSynth<T: Synth>(pub(crate) T, pub(crate) OwnedSpan);
+
+impl<T: Synth> Synth for SourceSynth<T> {
+    fn synth(&self, context: &mut SynthContext) -> bool {
+        let position = context.position();
+        let written = self.0.synth(context);
+        context.map_source(position, &self.1.trim_end());
+        written
+    }
+}
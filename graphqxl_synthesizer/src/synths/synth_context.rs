@@ -1,3 +1,4 @@
+use crate::synths::SourceMapEntry;
 use graphqxl_parser::OwnedSpan;
 
 #[derive(Clone)]
@@ -21,14 +22,6 @@ impl Default for SynthConfig {
     }
 }
 
-pub struct SourceMapEntry {
-    pub line: usize,
-    pub col: usize,
-    pub start: usize,
-    pub stop: usize,
-    pub span: OwnedSpan,
-}
-
 #[derive(Default)]
 pub(crate) struct SynthContext {
     pub(crate) result: String,
@@ -56,17 +49,38 @@ impl SynthContext {
     }
 
     pub(crate) fn write_with_source<'a>(&mut self, text: &'a str, span: &OwnedSpan) -> &'a str {
-        let start = self.offset;
+        let position = self.position();
         self.write(text);
-        let stop = self.offset;
+        self.map_source(position, span);
+        text
+    }
+
+    /// The offset, line and column where the next text is written.
+    pub(crate) fn position(&self) -> (usize, usize, usize) {
+        (self.offset, self.line, self.col)
+    }
+
+    /// Maps everything written since `position` to `span`.
+    pub(crate) fn map_source(&mut self, position: (usize, usize, usize), span: &OwnedSpan) {
+        self.push_source(position, span, false);
+    }
+
+    /// Maps the value type of a field or an argument, written since `position`, to `span`.
+    pub(crate) fn map_value_type(&mut self, position: (usize, usize, usize), span: &OwnedSpan) {
+        self.push_source(position, span, true);
+    }
+
+    fn push_source(&mut self, position: (usize, usize, usize), span: &OwnedSpan, value_type: bool) {
+        let (start, line, col) = position;
         self.source_map.push(SourceMapEntry {
-            line: self.line,
-            col: self.col,
+            line,
+            col,
             start,
-            stop,
+            stop: self.offset,
             span: span.clone(),
+            verbatim: self.result[start..] == span.input,
+            value_type,
         });
-        text
     }
 
     pub(crate) fn write_line_jump(&mut self) {
@@ -87,6 +101,7 @@ impl SynthContext {
 
 pub(crate) trait Synth {
     fn synth(&self, context: &mut SynthContext) -> bool;
+    #[cfg(test)]
     fn synth_zero(&self) -> String {
         let mut context = SynthContext::default();
         self.synth(&mut context);
@@ -1,11 +1,16 @@
 extern crate core;
 
 mod resolve_expandable_ref;
+mod resolve_inline_generics;
 mod resolve_modified_ref;
+mod resolve_modifier_def;
 mod transpile_block_def;
 mod transpile_description;
 mod transpile_generic_block_def;
+mod transpile_generic_union;
 mod transpile_spec;
 mod utils;
 
-pub use transpile_spec::{transpile_spec, TranspileSpecOptions};
+pub use transpile_spec::{
+    transpile_spec, transpile_spec_with_cache, TranspileCache, TranspileSpecOptions,
+};
@@ -1,9 +1,12 @@
 use crate::resolve_modified_ref::{
     resolve_modified_ref_with_context, ModifiedRefStackContext, ResolvedRef,
 };
-use crate::transpile_description::transpile_description;
-use crate::utils::BlockDefStore;
-use graphqxl_parser::{BlockEntry, ExpandableRef, ValueBasicType};
+use crate::transpile_description::{transpile_description, transpile_directive};
+use crate::utils::{
+    build_generic_map, check_generic_constraints, replace_generic_block_field,
+    replace_generic_directive, replace_generic_modified_ref, BlockDefStore,
+};
+use graphqxl_parser::{BlockEntry, ExpandableRef};
 use std::collections::HashMap;
 use std::error::Error;
 
@@ -17,43 +20,22 @@ pub(crate) fn resolve_expandable_ref(
     let referenced_block_def = match store.get(&expandable_ref.identifier.id) {
         Some(block_def) => block_def,
         None => {
-            return Err(expandable_ref
+            let err = expandable_ref
                 .identifier
                 .span
-                .make_error(&format!("{} is undefined", &expandable_ref.identifier.id)));
+                .make_error(&format!("{} is undefined", &expandable_ref.identifier.id));
+            return Err(stack_context.path.trace(err));
         }
     };
+    // errors found while expanding this reference point to how it was reached
+    let trace = |err| stack_context.path.trace(err);
 
-    let empty_args = vec![];
-    let generic_args = match &referenced_block_def.generic {
-        Some(generic) => &generic.args,
-        None => &empty_args,
-    };
-
+    let generic_map =
+        build_generic_map(referenced_block_def.generic.as_ref(), expandable_ref).map_err(trace)?;
+    check_generic_constraints(referenced_block_def.generic.as_ref(), &generic_map, store)
+        .map_err(trace)?;
     let generic_referenced_block_def = referenced_block_def;
 
-    let empty_call_args = vec![];
-    let generic_call_args = match &expandable_ref.generic_call {
-        Some(generic_call) => &generic_call.args,
-        None => &empty_call_args,
-    };
-
-    if generic_args.len() != generic_call_args.len() {
-        return Err(expandable_ref.span.make_error(&format!(
-            "Instantiated generic type with {} args when {} where expected",
-            generic_call_args.len(),
-            generic_args.len()
-        )));
-    }
-
-    let mut generic_map = HashMap::new();
-    for i in 0..generic_args.len() {
-        generic_map.insert(
-            &generic_args.get(i).unwrap().id,
-            generic_call_args.get(i).unwrap(),
-        );
-    }
-
     let mut resolved_ref = ResolvedRef::init(generic_referenced_block_def);
 
     let mut description_replacements = HashMap::new();
@@ -64,41 +46,53 @@ pub(crate) fn resolve_expandable_ref(
         );
     }
 
-    transpile_description(&mut resolved_ref, &description_replacements, true)?;
+    transpile_description(&mut resolved_ref, &description_replacements, true).map_err(trace)?;
+    for directive in resolved_ref.directives.iter_mut() {
+        transpile_directive(directive, &description_replacements).map_err(trace)?;
+        replace_generic_directive(directive, &generic_map).map_err(trace)?;
+    }
 
     let mut new_fields = vec![];
+    let mut origins = vec![];
 
     for entry in generic_referenced_block_def.entries.iter() {
         let new_entry = entry.clone();
         // if it is a field...
         match new_entry {
-            BlockEntry::Field(mut block_field) => {
-                transpile_description(&mut block_field, &description_replacements, true)?;
-                // ...and has a type...
-                if let Some(value_type) = &mut block_field.value_type {
-                    let basic_value_type = value_type.retrieve_basic_type();
-                    // ...and that type is an object...
-                    if let ValueBasicType::Object(object) = basic_value_type {
-                        // ...which is stored in the generic map...
-                        if let Some(generic_replacement) = generic_map.get(&object.id) {
-                            // ...then replace it
-                            value_type.replace_basic_type((*generic_replacement).clone())?;
-                        }
+            BlockEntry::Field(block_field) => {
+                let mut block_field = *block_field;
+                transpile_description(&mut block_field, &description_replacements, true)
+                    .map_err(trace)?;
+                for directive in block_field.directives.iter_mut() {
+                    transpile_directive(directive, &description_replacements).map_err(trace)?;
+                }
+                for arg in block_field.args.iter_mut() {
+                    transpile_description(arg, &description_replacements, true).map_err(trace)?;
+                    for directive in arg.directives.iter_mut() {
+                        transpile_directive(directive, &description_replacements).map_err(trace)?;
                     }
                 }
-                new_fields.push(block_field)
+                // ...replace the generic parameters in its type and in its arguments
+                replace_generic_block_field(&mut block_field, &generic_map).map_err(trace)?;
+                new_fields.push(block_field);
+                origins.push(stack_context.path.clone());
             }
             BlockEntry::SpreadRef(modified_ref) => {
+                // the spread might be instantiated with our own generic parameters
+                let modified_ref =
+                    replace_generic_modified_ref(&modified_ref, &generic_map).map_err(trace)?;
                 // NOTE: Careful here, recursive brain exploding ahead
                 let resolved_ref = resolve_modified_ref_with_context(
                     &modified_ref,
                     store,
-                    stack_context.plus_1(),
+                    stack_context.plus_1().spread(&modified_ref),
                 )?;
-                new_fields.extend_from_slice(&resolved_ref.fields);
+                new_fields.extend(resolved_ref.fields);
+                origins.extend(resolved_ref.origins);
             }
         }
     }
     resolved_ref.fields = new_fields;
+    resolved_ref.origins = origins;
     Ok(resolved_ref)
 }
@@ -0,0 +1,227 @@
+use crate::utils::BlockDefStore;
+use graphqxl_parser::{
+    BlockDefType, BlockField, ExpandableRef, GenericBlockDef, GenericKind, Identifier, ModifiedRef,
+    ValueBasicType, ValueType,
+};
+use std::error::Error;
+
+/// Computes the name of the concrete definition generated by an inline instantiation,
+/// nested instantiations in the arguments contribute with their own concrete names.
+/// Private generics and arguments lose their prefix, so that the instance is not private itself.
+fn instance_name(store: &BlockDefStore, expandable_ref: &ExpandableRef) -> String {
+    let mut args = String::new();
+    if let Some(generic_call) = &expandable_ref.generic_call {
+        for arg in generic_call.args.iter() {
+            args += store.public_name(&match arg.retrieve_basic_type() {
+                ValueBasicType::GenericObject(nested) => instance_name(store, nested),
+                basic_type => basic_type.to_string(),
+            });
+        }
+    }
+    store
+        .inline_generic_name()
+        .replace("{args}", &args)
+        .replace("{name}", store.public_name(&expandable_ref.identifier.id))
+}
+
+/// Replaces an inline generic instantiation, like `Connection<User>`, by a reference
+/// to a concrete definition, registering that definition in the store so that it gets
+/// generated once, no matter how many fields use it.
+fn resolve_inline_generic(
+    value_type: &mut ValueType,
+    store: &BlockDefStore,
+) -> Result<(), Box<dyn Error>> {
+    let expandable_ref = match value_type.retrieve_basic_type() {
+        ValueBasicType::GenericObject(expandable_ref) => (**expandable_ref).clone(),
+        _ => return Ok(()),
+    };
+
+    let identifier = &expandable_ref.identifier;
+    let kind = match store.get_kind(&identifier.id) {
+        Some(GenericKind::Type) => BlockDefType::Type,
+        Some(GenericKind::Input) => BlockDefType::Input,
+        Some(GenericKind::Interface) => BlockDefType::Interface,
+        Some(kind) => {
+            return Err(identifier.span.make_error(&format!(
+                "{} is {} {kind}, only types, inputs and interfaces can be instantiated inline",
+                identifier.id,
+                if kind == GenericKind::Enum { "an" } else { "a" }
+            )))
+        }
+        None => {
+            return Err(identifier
+                .span
+                .make_error(&format!("{} is undefined", identifier.id)))
+        }
+    };
+
+    let concrete_identifier = Identifier {
+        id: instance_name(store, &expandable_ref),
+        span: expandable_ref.span.clone(),
+    };
+    store.derive(GenericBlockDef {
+        span: expandable_ref.span.clone(),
+        description: "".to_string(),
+        description_variables: None,
+        kind,
+        name: concrete_identifier.clone(),
+        directives: vec![],
+        modified_ref: ModifiedRef::ExpandableRef(Box::new(expandable_ref)),
+    });
+    value_type.replace_basic_type(ValueType::Basic(
+        ValueBasicType::Object(concrete_identifier.clone()),
+        concrete_identifier.span,
+    ))?;
+    Ok(())
+}
+
+pub(crate) fn resolve_inline_generics(
+    block_field: &mut BlockField,
+    store: &BlockDefStore,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(value_type) = &mut block_field.value_type {
+        resolve_inline_generic(value_type, store)?;
+    }
+    for argument in block_field.args.iter_mut() {
+        resolve_inline_generic(&mut argument.value_type, store)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TranspileSpecOptions;
+    use graphqxl_parser::{BlockDef, Generic, Spec};
+    use std::cell::RefCell;
+
+    fn spec() -> Spec {
+        let mut spec = Spec::default();
+        spec.types.insert(
+            "Connection".to_string(),
+            BlockDef::type_def("Connection").generic(Generic::from("T")),
+        );
+        spec.types.insert(
+            "Edge".to_string(),
+            BlockDef::type_def("Edge").generic(Generic::from("T")),
+        );
+        spec.enums
+            .insert("Status".to_string(), BlockDef::enum_def("Status"));
+        spec
+    }
+
+    fn connection_of(arg: ValueType) -> ValueType {
+        ValueType::generic_object(ExpandableRef::from("Connection").generic_arg(arg))
+    }
+
+    #[test]
+    fn test_replaces_instantiation_by_concrete_name() {
+        let spec = spec();
+        let derived_defs = RefCell::new(vec![]);
+        let store = BlockDefStore::from(&spec.types)
+            .with_spec(&spec)
+            .with_derived_defs(&derived_defs);
+        let mut block_field = BlockField::build("users")
+            .value_type(connection_of(ValueType::object(Identifier::from("User"))).non_nullable());
+        resolve_inline_generics(&mut block_field, &store).unwrap();
+        assert_eq!(
+            block_field,
+            BlockField::build("users")
+                .value_type(ValueType::object(Identifier::from("UserConnection")).non_nullable())
+        );
+        let derived_defs = derived_defs.borrow();
+        assert_eq!(derived_defs.len(), 1);
+        assert_eq!(derived_defs[0].name.id, "UserConnection");
+        assert_eq!(derived_defs[0].kind, BlockDefType::Type);
+    }
+
+    #[test]
+    fn test_names_nested_instantiations() {
+        let spec = spec();
+        let derived_defs = RefCell::new(vec![]);
+        let store = BlockDefStore::from(&spec.types)
+            .with_spec(&spec)
+            .with_derived_defs(&derived_defs);
+        let mut block_field =
+            BlockField::build("edges").value_type(connection_of(ValueType::generic_object(
+                ExpandableRef::from("Edge")
+                    .generic_arg(ValueType::object(Identifier::from("User"))),
+            )));
+        resolve_inline_generics(&mut block_field, &store).unwrap();
+        assert_eq!(
+            block_field,
+            BlockField::build("edges")
+                .value_type(ValueType::object(Identifier::from("UserEdgeConnection")))
+        );
+        // the nested instantiation is derived once the fields of the outer one are transpiled
+        let names: Vec<String> = derived_defs
+            .borrow()
+            .iter()
+            .map(|def| def.name.id.clone())
+            .collect();
+        assert_eq!(names, vec!["UserEdgeConnection"]);
+    }
+
+    #[test]
+    fn test_uses_name_pattern() {
+        let options = TranspileSpecOptions {
+            inline_generic_name: "{name}Of{args}".to_string(),
+            ..Default::default()
+        };
+        let store = BlockDefStore::from(vec![]).with_options(&options);
+        assert_eq!(
+            instance_name(
+                &store,
+                &ExpandableRef::from("Page")
+                    .generic_arg(ValueType::object(Identifier::from("User")).non_nullable())
+                    .generic_arg(ValueType::string().array())
+            ),
+            "PageOfUserString"
+        )
+    }
+
+    #[test]
+    fn test_drops_the_private_prefixes() {
+        let options = TranspileSpecOptions {
+            private_prefix: "_".to_string(),
+            ..Default::default()
+        };
+        let store = BlockDefStore::from(vec![]).with_options(&options);
+        assert_eq!(
+            instance_name(
+                &store,
+                &ExpandableRef::from("Box").generic_arg(ValueType::generic_object(
+                    ExpandableRef::from("_Page")
+                        .generic_arg(ValueType::object(Identifier::from("_User")))
+                ))
+            ),
+            "UserPageBox"
+        );
+        let options = TranspileSpecOptions {
+            inline_generic_name: "{name}Of{args}".to_string(),
+            ..options
+        };
+        let store = BlockDefStore::from(vec![]).with_options(&options);
+        assert_eq!(
+            instance_name(
+                &store,
+                &ExpandableRef::from("_Page")
+                    .generic_arg(ValueType::object(Identifier::from("User")))
+            ),
+            "PageOfUser"
+        )
+    }
+
+    #[test]
+    fn test_fails_with_enums() {
+        let spec = spec();
+        let store = BlockDefStore::from(&spec.types).with_spec(&spec);
+        let mut block_field = BlockField::build("status").value_type(ValueType::generic_object(
+            ExpandableRef::from("Status").generic_arg(ValueType::int()),
+        ));
+        let err = resolve_inline_generics(&mut block_field, &store).unwrap_err();
+        assert!(err.to_string().contains(
+            "Status is an enum, only types, inputs and interfaces can be instantiated inline"
+        ))
+    }
+}
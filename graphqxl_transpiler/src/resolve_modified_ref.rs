@@ -1,9 +1,12 @@
 use crate::resolve_expandable_ref::resolve_expandable_ref;
-use crate::utils::BlockDefStore;
+use crate::resolve_modifier_def::resolve_modifier_def;
+use crate::utils::{BlockDefStore, ExpansionPath};
 use graphqxl_parser::{
-    BlockDef, BlockField, Directive, Implements, ModifiedRef, OwnedSpan, ValueType,
+    BlockDef, BlockDefType, BlockField, Directive, ExpandableRef, GenericBlockDef, Identifier,
+    Implements, ModifiedRef, OwnedSpan, ValueBasicType, ValueType,
 };
 use std::error::Error;
+use std::mem;
 use std::ops::Deref;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -12,6 +15,8 @@ pub(crate) struct ResolvedRef {
     pub implements: Option<Implements>,
     pub description: String,
     pub fields: Vec<BlockField>,
+    /// How each of the fields got here, in the same order
+    pub origins: Vec<ExpansionPath>,
     pub directives: Vec<Directive>,
 }
 
@@ -22,9 +27,18 @@ impl ResolvedRef {
             implements: value.implements.clone(),
             description: value.description.clone(),
             fields: vec![],
+            origins: vec![],
             directives: value.directives.clone(),
         }
     }
+
+    /// Keeps only the fields that satisfy `keep`, along with their origins.
+    fn retain_fields(&mut self, keep: impl Fn(&BlockField) -> bool) {
+        let entries = mem::take(&mut self.fields)
+            .into_iter()
+            .zip(mem::take(&mut self.origins));
+        (self.fields, self.origins) = entries.filter(|(field, _)| keep(field)).unzip();
+    }
 }
 
 fn nullable(resolved_ref: &ResolvedRef) -> ResolvedRef {
@@ -53,17 +67,184 @@ fn non_nullable(resolved_ref: &ResolvedRef) -> ResolvedRef {
     required_block_def
 }
 
+const DEEP_OPTIONAL_SUFFIX: &str = "Patch";
+const DEEP_REQUIRED_SUFFIX: &str = "Required";
+
+fn deep_nullable_value_type(value_type: &ValueType) -> ValueType {
+    match value_type {
+        ValueType::Basic(_, _) => value_type.clone(),
+        ValueType::Array(inner, span) => {
+            ValueType::Array(Box::new(deep_nullable_value_type(inner)), span.clone())
+        }
+        ValueType::NonNullable(inner, _) => deep_nullable_value_type(inner),
+    }
+}
+
+fn deep_non_nullable_value_type(value_type: &ValueType) -> ValueType {
+    let nullable = match value_type {
+        ValueType::Basic(_, _) => value_type.clone(),
+        ValueType::Array(inner, span) => {
+            ValueType::Array(Box::new(deep_non_nullable_value_type(inner)), span.clone())
+        }
+        ValueType::NonNullable(inner, _) => return deep_non_nullable_value_type(inner),
+    };
+    nullable.non_nullable()
+}
+
+/// Points the value type to a derived copy of the input it references, if it references
+/// one, registering that copy in the store so that it also gets generated.
+fn derive_nested_input(
+    value_type: &mut ValueType,
+    store: &BlockDefStore,
+    suffix: &str,
+    modifier: fn(&mut ModifiedRef) -> ModifiedRef,
+) -> Result<(), Box<dyn Error>> {
+    let identifier = match value_type.retrieve_basic_type() {
+        ValueBasicType::Object(identifier) => identifier.clone(),
+        _ => return Ok(()),
+    };
+    match store.get(&identifier.id) {
+        Some(block_def) if block_def.kind == BlockDefType::Input => {}
+        _ => return Ok(()),
+    };
+    let derived_identifier = Identifier {
+        id: format!("{}{}", store.public_name(&identifier.id), suffix),
+        span: identifier.span.clone(),
+    };
+    store.derive(GenericBlockDef {
+        span: identifier.span.clone(),
+        description: "".to_string(),
+        description_variables: None,
+        kind: BlockDefType::Input,
+        name: derived_identifier.clone(),
+        directives: vec![],
+        modified_ref: modifier(&mut ModifiedRef::ExpandableRef(Box::new(ExpandableRef {
+            span: identifier.span.clone(),
+            identifier,
+            generic_call: None,
+        }))),
+    });
+    value_type.replace_basic_type(ValueType::Basic(
+        ValueBasicType::Object(derived_identifier.clone()),
+        derived_identifier.span,
+    ))?;
+    Ok(())
+}
+
+fn deep_nullable(
+    resolved_ref: &ResolvedRef,
+    store: &BlockDefStore,
+) -> Result<ResolvedRef, Box<dyn Error>> {
+    let mut optional_block_def = resolved_ref.clone();
+    for field in optional_block_def.fields.iter_mut() {
+        if let Some(value_type) = &mut field.value_type {
+            *value_type = deep_nullable_value_type(value_type);
+            derive_nested_input(
+                value_type,
+                store,
+                DEEP_OPTIONAL_SUFFIX,
+                ModifiedRef::deep_optional,
+            )?;
+        }
+    }
+    Ok(optional_block_def)
+}
+
+fn deep_non_nullable(
+    resolved_ref: &ResolvedRef,
+    store: &BlockDefStore,
+) -> Result<ResolvedRef, Box<dyn Error>> {
+    let mut required_block_def = resolved_ref.clone();
+    for field in required_block_def.fields.iter_mut() {
+        if let Some(value_type) = &mut field.value_type {
+            *value_type = deep_non_nullable_value_type(value_type);
+            derive_nested_input(
+                value_type,
+                store,
+                DEEP_REQUIRED_SUFFIX,
+                ModifiedRef::deep_required,
+            )?;
+        }
+    }
+    Ok(required_block_def)
+}
+
+fn check_selected_fields(
+    resolved_ref: &ResolvedRef,
+    selected_fields: &[Identifier],
+) -> Result<(), Box<dyn Error>> {
+    for selected_field in selected_fields.iter() {
+        if !resolved_ref
+            .fields
+            .iter()
+            .any(|field| field.name.id == selected_field.id)
+        {
+            return Err(selected_field
+                .span
+                .make_error(&format!("field {} does not exist", selected_field.id)));
+        }
+    }
+    Ok(())
+}
+
+fn pick(
+    resolved_ref: &ResolvedRef,
+    selected_fields: &[Identifier],
+) -> Result<ResolvedRef, Box<dyn Error>> {
+    check_selected_fields(resolved_ref, selected_fields)?;
+    let mut picked_block_def = resolved_ref.clone();
+    picked_block_def.retain_fields(|field| selected_fields.iter().any(|s| s.id == field.name.id));
+    Ok(picked_block_def)
+}
+
+fn omit(
+    resolved_ref: &ResolvedRef,
+    selected_fields: &[Identifier],
+) -> Result<ResolvedRef, Box<dyn Error>> {
+    check_selected_fields(resolved_ref, selected_fields)?;
+    let mut omitted_block_def = resolved_ref.clone();
+    omitted_block_def.retain_fields(|field| !selected_fields.iter().any(|s| s.id == field.name.id));
+    Ok(omitted_block_def)
+}
+
 #[derive(Default, Clone)]
 pub(crate) struct ModifiedRefStackContext {
     stack_count: usize,
+    pub(crate) path: ExpansionPath,
 }
 
 impl ModifiedRefStackContext {
+    /// Starts resolving the references found in the definition named `name`.
+    pub(crate) fn expanding(kind: &BlockDefType, name: &Identifier) -> Self {
+        Self {
+            stack_count: 0,
+            path: ExpansionPath::default().step(&format!("{kind} {}", name.id), &name.span),
+        }
+    }
+
     pub(crate) fn plus_1(&self) -> Self {
         let mut clone = self.clone();
         clone.stack_count += 1;
         clone
     }
+
+    /// Goes into the definition referenced by `modified_ref`.
+    pub(crate) fn reference(&self, modified_ref: &ModifiedRef) -> Self {
+        let mut clone = self.clone();
+        clone.path = clone
+            .path
+            .step(&modified_ref.to_string(), modified_ref.span());
+        clone
+    }
+
+    /// Goes into the spread of `modified_ref`.
+    pub(crate) fn spread(&self, modified_ref: &ModifiedRef) -> Self {
+        let mut clone = self.clone();
+        clone.path = clone
+            .path
+            .step(&format!("...{modified_ref}"), modified_ref.span());
+        clone
+    }
 }
 
 const MAX_RECURSION_DEPTH: usize = 100;
@@ -87,17 +268,31 @@ pub(crate) fn resolve_modified_ref_with_context(
             store,
             stack_context.plus_1(),
         )?)),
-        ModifiedRef::ExpandableRef(expandable_ref) => Ok(resolve_expandable_ref(
-            expandable_ref,
+        ModifiedRef::DeepRequired(modified_ref, _) => deep_non_nullable(
+            &resolve_modified_ref_with_context(modified_ref, store, stack_context.plus_1())?,
             store,
-            stack_context.plus_1(),
-        )?),
+        ),
+        ModifiedRef::DeepOptional(modified_ref, _) => deep_nullable(
+            &resolve_modified_ref_with_context(modified_ref, store, stack_context.plus_1())?,
+            store,
+        ),
+        ModifiedRef::Pick(modified_ref, selected_fields, _) => pick(
+            &resolve_modified_ref_with_context(modified_ref, store, stack_context.plus_1())?,
+            selected_fields,
+        ),
+        ModifiedRef::Omit(modified_ref, selected_fields, _) => omit(
+            &resolve_modified_ref_with_context(modified_ref, store, stack_context.plus_1())?,
+            selected_fields,
+        ),
+        ModifiedRef::ExpandableRef(expandable_ref) => {
+            match store.get_modifier(&expandable_ref.identifier.id) {
+                Some(modifier_def) => resolve_modified_ref_with_context(
+                    &resolve_modifier_def(modifier_def, expandable_ref, store)?,
+                    store,
+                    stack_context.plus_1(),
+                ),
+                None => resolve_expandable_ref(expandable_ref, store, stack_context.plus_1()),
+            }
+        }
     }
 }
-
-pub(crate) fn resolve_modified_ref(
-    modified_ref: &ModifiedRef,
-    store: &BlockDefStore,
-) -> Result<ResolvedRef, Box<dyn Error>> {
-    resolve_modified_ref_with_context(modified_ref, store, ModifiedRefStackContext::default())
-}
@@ -0,0 +1,97 @@
+use crate::utils::{
+    build_generic_map, check_generic_constraints, replace_generic_modified_ref, BlockDefStore,
+};
+use graphqxl_parser::{ExpandableRef, ModifiedRef, ModifierDef};
+use std::error::Error;
+
+pub(crate) fn resolve_modifier_def(
+    modifier_def: &ModifierDef,
+    expandable_ref: &ExpandableRef,
+    store: &BlockDefStore,
+) -> Result<ModifiedRef, Box<dyn Error>> {
+    let generic_map = build_generic_map(Some(&modifier_def.generic), expandable_ref)?;
+    check_generic_constraints(Some(&modifier_def.generic), &generic_map, store)?;
+    replace_generic_modified_ref(&modifier_def.modified_ref, &generic_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqxl_parser::{Generic, Identifier, ValueType};
+
+    fn mutable_modifier_def() -> ModifierDef {
+        ModifierDef::build(
+            "Mutable",
+            Generic::from("T"),
+            ModifiedRef::build("T").omit(&["id"]).optional(),
+        )
+    }
+
+    #[test]
+    fn test_replaces_modifier_arg() {
+        let resolved = resolve_modifier_def(
+            &mutable_modifier_def(),
+            &ExpandableRef::from("Mutable")
+                .generic_arg(ValueType::object(Identifier::from("User"))),
+            &BlockDefStore::from(vec![]),
+        )
+        .unwrap();
+        assert_eq!(
+            resolved,
+            ModifiedRef::build("User").omit(&["id"]).optional()
+        )
+    }
+
+    #[test]
+    fn test_replaces_modifier_arg_in_generic_calls() {
+        let modifier_def = ModifierDef::build(
+            "RequiredPage",
+            Generic::from("T"),
+            ModifiedRef::expandable_ref(
+                ExpandableRef::from("Page").generic_arg(ValueType::object(Identifier::from("T"))),
+            )
+            .required(),
+        );
+        let resolved = resolve_modifier_def(
+            &modifier_def,
+            &ExpandableRef::from("RequiredPage")
+                .generic_arg(ValueType::object(Identifier::from("User")).non_nullable()),
+            &BlockDefStore::from(vec![]),
+        )
+        .unwrap();
+        assert_eq!(
+            resolved,
+            ModifiedRef::expandable_ref(
+                ExpandableRef::from("Page")
+                    .generic_arg(ValueType::object(Identifier::from("User")).non_nullable()),
+            )
+            .required()
+        )
+    }
+
+    #[test]
+    fn test_fails_with_wrong_number_of_args() {
+        let err = resolve_modifier_def(
+            &mutable_modifier_def(),
+            &ExpandableRef::from("Mutable"),
+            &BlockDefStore::from(vec![]),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Generic parameter T of Mutable is left unbound, expected Mutable<T>"))
+    }
+
+    #[test]
+    fn test_fails_if_modified_arg_is_not_an_object() {
+        let err = resolve_modifier_def(
+            &mutable_modifier_def(),
+            &ExpandableRef::from("Mutable").generic_arg(ValueType::string()),
+            &BlockDefStore::from(vec![]),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "generic parameter T is expanded, so it can only be bound to a type or an input"
+        ))
+    }
+}
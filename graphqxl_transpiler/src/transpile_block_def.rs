@@ -1,8 +1,9 @@
-use crate::resolve_modified_ref::resolve_modified_ref;
+use crate::resolve_inline_generics::resolve_inline_generics;
+use crate::resolve_modified_ref::{resolve_modified_ref_with_context, ModifiedRefStackContext};
 use crate::transpile_description::transpile_description;
-use crate::utils::BlockDefStore;
-use graphqxl_parser::{BlockDef, BlockEntry, Identifier};
-use std::collections::{HashMap, HashSet};
+use crate::utils::{BlockDefStore, ExpansionPath};
+use graphqxl_parser::{BlockDef, BlockEntry, BlockField, Identifier, LocatedError};
+use std::collections::HashMap;
 use std::error::Error;
 use std::string::ToString;
 
@@ -39,21 +40,32 @@ fn transpile_block_def(
     let mut transpiled_block_def = block_def.clone();
     transpiled_block_def.entries.clear();
 
+    // each field along with the spreads it went through to get here
     let mut entries_to_evaluate = vec![];
+    let stack_context = ModifiedRefStackContext::expanding(&block_def.kind, &block_def.name);
 
     for entry in block_def.entries.iter() {
         match entry {
             BlockEntry::SpreadRef(modified_ref) => {
-                let referenced_type = resolve_modified_ref(modified_ref, store)?;
-                entries_to_evaluate.extend(referenced_type.fields);
+                let referenced_type = resolve_modified_ref_with_context(
+                    modified_ref,
+                    store,
+                    stack_context.spread(modified_ref),
+                )?;
+                entries_to_evaluate.extend(
+                    referenced_type
+                        .fields
+                        .into_iter()
+                        .zip(referenced_type.origins),
+                );
             }
             BlockEntry::Field(field) => {
-                entries_to_evaluate.push(field.clone());
+                entries_to_evaluate.push((field.as_ref().clone(), stack_context.path.clone()));
             }
         }
     }
 
-    let mut seen = HashSet::new();
+    let mut seen = HashMap::new();
 
     let block_type = &block_def.kind;
     let mut template_string_replacements = HashMap::from([
@@ -73,19 +85,53 @@ fn transpile_block_def(
         false,
     )?;
 
-    for field in entries_to_evaluate.iter_mut() {
-        if seen.contains(&field.name.id) {
-            return Err(field.span.make_error("repeated field"));
+    for (field, path) in entries_to_evaluate.iter_mut() {
+        if let Some((first, first_path)) = seen.get(&field.name.id) {
+            return Err(repeated_field_error(field, path, first, first_path));
         }
-        seen.insert(field.name.id.clone());
+        seen.insert(field.name.id.clone(), (field.clone(), path.clone()));
+        resolve_inline_generics(field, store)?;
         transpile_description(field, &template_string_replacements, false)?;
         transpiled_block_def
             .entries
-            .push(BlockEntry::Field(field.clone()));
+            .push(BlockEntry::Field(Box::new(field.clone())));
     }
     Ok(transpiled_block_def)
 }
 
+/// Points to the fields, or to the spreads they come from if they are in another block,
+/// along with the spreads they went through when those are nested.
+fn repeated_field_error(
+    field: &BlockField,
+    path: &ExpansionPath,
+    first: &BlockField,
+    first_path: &ExpansionPath,
+) -> Box<LocatedError> {
+    let name = &field.name.id;
+    let msg = format!("repeated field {name}");
+    let mut err = match path.outermost() {
+        Some(spread) => path
+            .label_spreads(spread.span.make_error(&msg))
+            .with_label(field.span.make_label("the field is defined here")),
+        None => field.span.make_error(&msg),
+    };
+    err = match first_path.outermost() {
+        Some(first_spread) => {
+            err.with_label(first_spread.span.make_label("first spread from here"))
+        }
+        None => err.with_label(first.span.make_label("first defined here")),
+    };
+    if path.is_nested() {
+        err = err.with_note(&format!("expansion path: {path} -> field `{name}`"));
+    }
+    if first_path.is_nested() {
+        err = err.with_note(&format!(
+            "first expansion path: {first_path} -> field `{name}`"
+        ));
+    }
+    err
+}
+
 pub(crate) fn transpile_block_def_by_id(
     identifier: &Identifier,
     store: &BlockDefStore,
@@ -211,6 +257,29 @@ mod tests {
         assert!(err.to_string().contains("repeated field"))
     }
 
+    #[test]
+    fn test_traces_the_spreads_a_repeated_field_went_through() {
+        let c = BlockDef::type_def("C").field(BlockField::build("field").string());
+        let b = BlockDef::type_def("B").spread(ModifiedRef::build("C"));
+        let a = BlockDef::type_def("A")
+            .field(BlockField::build("field").string())
+            .spread(ModifiedRef::build("B").required());
+        let mut types = HashMap::new();
+        for block_def in [a, b, c] {
+            types.insert(block_def.name.id.clone(), block_def);
+        }
+        let err = transpile_block_def(
+            &IdOrBlock::Id(&Identifier::from("A")),
+            &BlockDefStore::from(&types),
+        )
+        .unwrap_err();
+        let err = err.downcast::<LocatedError>().unwrap();
+        assert_eq!(
+            err.notes,
+            vec!["expansion path: type A -> ...Required<B> -> ...C -> field `field`"]
+        );
+    }
+
     #[test]
     fn test_undefined_spread_should_fail() {
         let block_def = BlockDef::type_def("MyType2")
@@ -1,12 +1,11 @@
 use crate::resolve_modified_ref::ResolvedRef;
-use graphqxl_parser::{BlockDef, BlockField, OwnedSpan};
+use graphqxl_parser::{
+    Argument, BlockDef, BlockField, Directive, OwnedSpan, ValueBasicData, ValueData,
+};
 use regex::{escape, Regex};
 use std::collections::HashMap;
 use std::error::Error;
 
-#[derive(Debug)]
-struct Struct;
-
 pub(crate) trait TemplateDescription {
     fn get_description(&self) -> &str;
     fn mutate_description(&mut self, new_description: &str);
@@ -33,19 +32,18 @@ macro_rules! impl_template_description {
 
 impl_template_description!(BlockField);
 impl_template_description!(BlockDef);
+impl_template_description!(Argument);
 impl_template_description!(ResolvedRef);
 
-pub(crate) fn transpile_description<T: TemplateDescription>(
-    with_template_description: &mut T,
+fn replace_templates(
+    text: &str,
     replace: &HashMap<String, String>,
+    span: &OwnedSpan,
     allow_missing_replacements: bool,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<String, Box<dyn Error>> {
     let any_template: Regex = Regex::new(r"\$\{\{.*}}").unwrap();
 
-    let mut replaced = with_template_description.get_description().to_string();
-    if replaced.is_empty() {
-        return Ok(());
-    }
+    let mut replaced = text.to_string();
     for (key, value) in replace.iter() {
         let escaped_key = escape(key);
 
@@ -53,22 +51,73 @@ pub(crate) fn transpile_description<T: TemplateDescription>(
         let re = match re_or_err {
             Ok(re) => re,
             Err(err) => {
-                return Err(with_template_description
-                    .owned_span()
-                    .make_error(&err.to_string()));
+                return Err(span.make_error(&err.to_string()));
             }
         };
         replaced = re.replace_all(&replaced, value).to_string();
     }
     if !allow_missing_replacements && any_template.is_match(&replaced) {
-        return Err(with_template_description
-            .owned_span()
-            .make_error("Not all the template variables where resolved"));
+        return Err(span.make_error("Not all the template variables where resolved"));
     }
+    Ok(replaced)
+}
+
+pub(crate) fn transpile_description<T: TemplateDescription>(
+    with_template_description: &mut T,
+    replace: &HashMap<String, String>,
+    allow_missing_replacements: bool,
+) -> Result<(), Box<dyn Error>> {
+    if with_template_description.get_description().is_empty() {
+        return Ok(());
+    }
+    let replaced = replace_templates(
+        with_template_description.get_description(),
+        replace,
+        with_template_description.owned_span(),
+        allow_missing_replacements,
+    )?;
     with_template_description.mutate_description(&replaced);
     Ok(())
 }
 
+fn transpile_value_data(
+    value_data: &mut ValueData,
+    replace: &HashMap<String, String>,
+    span: &OwnedSpan,
+) -> Result<(), Box<dyn Error>> {
+    match value_data {
+        ValueData::Basic(ValueBasicData::String(string)) => {
+            *string = replace_templates(string, replace, span, true)?;
+        }
+        ValueData::Basic(_) => {}
+        ValueData::List(list) => {
+            for value_data in list.iter_mut() {
+                transpile_value_data(value_data, replace, span)?;
+            }
+        }
+        ValueData::Object(object) => {
+            for value_data in object.values_mut() {
+                transpile_value_data(value_data, replace, span)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the templates found in the string inputs of a directive call,
+/// templates that cannot be resolved yet are left untouched.
+pub(crate) fn transpile_directive(
+    directive: &mut Directive,
+    replace: &HashMap<String, String>,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(call) = &mut directive.call {
+        for input in call.inputs.iter_mut() {
+            transpile_value_data(&mut input.value, replace, &input.span)?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,7 +167,7 @@ mod tests {
             Ok(_) => panic!("should have failed"),
             Err(err) => {
                 assert!(format!("{}", err)
-                    .ends_with(":0 Not all the template variables where resolved"),)
+                    .starts_with("error: Not all the template variables where resolved\n"))
             }
         }
     }
@@ -1,6 +1,6 @@
 use std::error::Error;
 
-use crate::resolve_modified_ref::resolve_modified_ref;
+use crate::resolve_modified_ref::{resolve_modified_ref_with_context, ModifiedRefStackContext};
 use crate::utils::BlockDefStore;
 use graphqxl_parser::{BlockDef, BlockEntry, GenericBlockDef};
 
@@ -8,7 +8,11 @@ pub(crate) fn transpile_generic_block_def(
     generic_block_def: &GenericBlockDef,
     store: &BlockDefStore,
 ) -> Result<BlockDef, Box<dyn Error>> {
-    let resolved = resolve_modified_ref(&generic_block_def.modified_ref, store)?;
+    let modified_ref = &generic_block_def.modified_ref;
+    let stack_context =
+        ModifiedRefStackContext::expanding(&generic_block_def.kind, &generic_block_def.name)
+            .reference(modified_ref);
+    let resolved = resolve_modified_ref_with_context(modified_ref, store, stack_context)?;
 
     let mut directives = resolved.directives.clone();
     directives.extend(generic_block_def.directives.clone());
@@ -32,7 +36,7 @@ pub(crate) fn transpile_generic_block_def(
         entries: resolved
             .fields
             .iter()
-            .map(|el| BlockEntry::Field(el.clone()))
+            .map(|el| BlockEntry::Field(Box::new(el.clone())))
             .collect(),
     })
 }
@@ -0,0 +1,119 @@
+use crate::utils::{build_generic_map, check_generic_constraints, BlockDefStore};
+use graphqxl_parser::{GenericUnion, Identifier, Union, ValueBasicType, ValueType};
+use std::collections::HashMap;
+use std::error::Error;
+
+pub(crate) fn transpile_generic_union(
+    generic_union: &GenericUnion,
+    store: &BlockDefStore,
+) -> Result<Union, Box<dyn Error>> {
+    let expandable_ref = &generic_union.expandable_ref;
+    let referenced_union = match store.get_union(&expandable_ref.identifier.id) {
+        Some(union) => union,
+        None => {
+            return Err(expandable_ref
+                .identifier
+                .span
+                .make_error(&format!("{} is undefined", &expandable_ref.identifier.id)));
+        }
+    };
+
+    let generic_map = build_generic_map(referenced_union.generic.as_ref(), expandable_ref)?;
+    check_generic_constraints(referenced_union.generic.as_ref(), &generic_map, store)?;
+    let mut replacements: HashMap<&String, &Identifier> = HashMap::new();
+    for (arg, value_type) in generic_map {
+        match value_type {
+            ValueType::Basic(ValueBasicType::Object(identifier), _) => {
+                replacements.insert(arg, identifier);
+            }
+            _ => {
+                return Err(value_type
+                    .span()
+                    .make_error("unions can only be instantiated with object types"))
+            }
+        }
+    }
+
+    let types = referenced_union
+        .types
+        .iter()
+        .map(|type_| match replacements.get(&type_.id) {
+            Some(replacement) => (*replacement).clone(),
+            None => type_.clone(),
+        })
+        .collect();
+
+    let mut directives = referenced_union.directives.clone();
+    directives.extend(generic_union.directives.clone());
+
+    let description = if !generic_union.description.is_empty() {
+        generic_union.description.clone()
+    } else {
+        referenced_union.description.clone()
+    };
+
+    Ok(Union {
+        extend: false,
+        span: generic_union.span.clone(),
+        name: generic_union.name.clone(),
+        generic: None,
+        description,
+        types,
+        directives,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqxl_parser::{Generic, Spec};
+
+    fn spec() -> Spec {
+        let mut spec = Spec::default();
+        spec.unions.insert(
+            "Result".to_string(),
+            Union::build("Result")
+                .generic(Generic::from("T"))
+                .type_("T")
+                .type_("Error"),
+        );
+        spec
+    }
+
+    #[test]
+    fn test_replaces_generic_members() {
+        let generic_union = GenericUnion::build("UserResult", "Result")
+            .arg(ValueType::object(Identifier::from("User")));
+        assert_eq!(
+            transpile_generic_union(
+                &generic_union,
+                &BlockDefStore::from(vec![]).with_spec(&spec())
+            )
+            .unwrap(),
+            Union::build("UserResult").type_("User").type_("Error")
+        )
+    }
+
+    #[test]
+    fn test_fails_with_wrong_number_of_args() {
+        let generic_union = GenericUnion::build("UserResult", "Result")
+            .arg(ValueType::object(Identifier::from("User")))
+            .arg(ValueType::object(Identifier::from("Other")));
+        transpile_generic_union(
+            &generic_union,
+            &BlockDefStore::from(vec![]).with_spec(&spec()),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_fails_with_non_object_args() {
+        let generic_union = GenericUnion::build("UserResult", "Result")
+            .arg(ValueType::object(Identifier::from("User")).non_nullable());
+        transpile_generic_union(
+            &generic_union,
+            &BlockDefStore::from(vec![]).with_spec(&spec()),
+        )
+        .unwrap_err();
+    }
+}
@@ -1,112 +1,644 @@
 use crate::transpile_block_def::{transpile_block_def_by_block, transpile_block_def_by_id};
 use crate::transpile_generic_block_def::transpile_generic_block_def;
-use crate::utils::BlockDefStore;
-use graphqxl_parser::{DefType, Spec};
-use std::collections::HashMap;
+use crate::transpile_generic_union::transpile_generic_union;
+use crate::utils::{BlockDefStore, DEFAULT_INLINE_GENERIC_NAME};
+use graphqxl_parser::{
+    BlockDef, BlockDefType, DefType, ErrorList, GenericBlockDef, ModifiedRef, OwnedSpan, Spec,
+    ValueBasicType,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
-#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TranspileSpecOptions {
     pub private_prefix: String,
+    /// Pattern for naming the definitions generated by inline generic instantiations like
+    /// `Connection<User>`, `{name}` is the generic definition's name and `{args}` the
+    /// concatenated names of the arguments.
+    pub inline_generic_name: String,
 }
 
-pub fn transpile_spec(spec: &Spec, options: &TranspileSpecOptions) -> Result<Spec, Box<dyn Error>> {
-    let mut target = Spec::default();
-    let mut transpiled_store = HashMap::new();
+impl Default for TranspileSpecOptions {
+    fn default() -> Self {
+        Self {
+            private_prefix: "".to_string(),
+            inline_generic_name: DEFAULT_INLINE_GENERIC_NAME.to_string(),
+        }
+    }
+}
 
-    for def in spec.order.iter() {
-        let types_block_def_store = BlockDefStore::from(vec![
-            &spec.types,
-            &transpiled_store,
-            &spec.interfaces,
-            &spec.inputs,
-        ]);
+/// Identifies what a derived definition is made of, so that two different instantiations
+/// that end up with the same name can be told apart.
+fn derived_signature(derived: &GenericBlockDef) -> Option<String> {
+    match &derived.modified_ref {
+        ModifiedRef::ExpandableRef(expandable_ref) if expandable_ref.generic_call.is_some() => {
+            Some(ValueBasicType::GenericObject(expandable_ref.clone()).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Whether the derived definition is already declared with the same name and made in the
+/// same way, like an `input AddressPatch = DeepOptional<Address>` that would otherwise
+/// be derived for the `Address` fields of another `DeepOptional` input.
+fn is_declared_as(spec: &Spec, derived: &GenericBlockDef) -> bool {
+    let declared = match derived.kind {
+        BlockDefType::Input => spec.generic_inputs.get(&derived.name.id),
+        BlockDefType::Interface => spec.generic_interfaces.get(&derived.name.id),
+        _ => spec.generic_types.get(&derived.name.id),
+    };
+    declared.is_some_and(|declared| {
+        declared.modified_ref.to_string() == derived.modified_ref.to_string()
+    })
+}
+
+/// A derived definition, along with the definition that generated it first.
+#[derive(Clone)]
+struct DerivedName {
+    signature: Option<String>,
+    owner: String,
+}
 
-        let inputs_block_def_store =
-            BlockDefStore::from(vec![&spec.inputs, &transpiled_store, &spec.types]);
+/// What a definition was transpiled into, including the definitions derived from it.
+#[derive(Clone)]
+struct CachedDef {
+    def: DefType,
+    output: Spec,
+    /// The transpiled generic definition, which other definitions can reference.
+    stored: Option<BlockDef>,
+    derived: Vec<(String, DerivedName)>,
+    /// Every definition it read, directly or through the definitions it references.
+    deps: HashSet<String>,
+}
 
-        let enums_block_def_store = BlockDefStore::from(&spec.enums);
+/// Definitions that were already transpiled, along with the spec they come from. It can
+/// be kept between compilations, so that only the definitions affected by the changes
+/// made since the previous one are transpiled again.
+#[derive(Default)]
+pub struct TranspileCache {
+    spec: Spec,
+    options: Option<TranspileSpecOptions>,
+    defs: HashMap<String, CachedDef>,
+    misses: usize,
+}
 
-        let interfaces_block_def_store = BlockDefStore::from(&spec.interfaces);
+impl TranspileCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
+    /// Number of times a definition had to be transpiled because it was not cached yet or
+    /// because it was affected by a change.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+fn def_key(def: &DefType) -> &str {
+    match def {
+        DefType::Type(name)
+        | DefType::GenericType(name)
+        | DefType::Input(name)
+        | DefType::GenericInput(name)
+        | DefType::Enum(name)
+        | DefType::Interface(name)
+        | DefType::GenericInterface(name)
+        | DefType::Scalar(name)
+        | DefType::Union(name)
+        | DefType::GenericUnion(name)
+        | DefType::Directive(name)
+        | DefType::Modifier(name) => &name.id,
+        DefType::Schema(name) => name,
+    }
+}
+
+/// Spans are equal no matter where they are, but the errors and the source map of the
+/// generated schema point to them, so a definition that moved has changed too.
+fn same_place(before: &OwnedSpan, after: &OwnedSpan) -> bool {
+    before.file == after.file
+        && before.start == after.start
+        && (before.line, before.col) == (after.line, after.col)
+        && before.input == after.input
+}
+
+/// Names of the definitions that were added, removed or modified from one spec to another.
+fn changed_names(previous: &Spec, spec: &Spec) -> HashSet<String> {
+    let mut changed = HashSet::new();
+    macro_rules! compare {
+        ($($field:ident),*) => {
+            $(
+                for name in previous.$field.keys().chain(spec.$field.keys()) {
+                    match (previous.$field.get(name), spec.$field.get(name)) {
+                        (Some(before), Some(after))
+                            if before == after && same_place(&before.span, &after.span) => {}
+                        _ => {
+                            changed.insert(name.clone());
+                        }
+                    }
+                }
+            )*
+        };
+    }
+    compare!(
+        types,
+        generic_types,
+        inputs,
+        generic_inputs,
+        enums,
+        interfaces,
+        generic_interfaces,
+        scalars,
+        unions,
+        generic_unions,
+        directives,
+        modifiers,
+        schemas
+    );
+    changed
+}
+
+/// Adds the definitions of `output` at the end of `target`.
+fn append(target: &mut Spec, output: &Spec) {
+    for def in output.order.iter() {
         match def {
             DefType::Type(name) => {
-                if name.id.starts_with(&options.private_prefix) {
-                    continue;
-                }
-                let transpiled = transpile_block_def_by_id(name, &types_block_def_store)?;
-                if transpiled.generic.is_none() {
-                    target.types.insert(name.id.clone(), transpiled);
-                    target.order.push(DefType::Type(name.clone()));
-                }
-            }
-            DefType::GenericType(name) => {
-                let generic_type = if let Some(generic_type) = spec.generic_types.get(&name.id) {
-                    generic_type
-                } else {
-                    return Err(name.span.make_error("generic type not found"));
-                };
-                let resolved = transpile_generic_block_def(generic_type, &types_block_def_store)?;
-                let transpiled = transpile_block_def_by_block(&resolved, &types_block_def_store)?;
-                transpiled_store.insert(name.id.clone(), transpiled.clone());
+                let transpiled = output.types[&name.id].clone();
                 target.types.insert(name.id.clone(), transpiled);
-                target.order.push(DefType::Type(name.clone()));
             }
             DefType::Input(name) => {
-                if name.id.starts_with(&options.private_prefix) {
-                    continue;
-                }
-                let transpiled = transpile_block_def_by_id(name, &inputs_block_def_store)?;
-                if transpiled.generic.is_none() {
-                    target.inputs.insert(name.id.clone(), transpiled);
-                    target.order.push(DefType::Input(name.clone()));
-                }
-            }
-            DefType::GenericInput(name) => {
-                let generic_input = if let Some(generic_input) = spec.generic_inputs.get(&name.id) {
-                    generic_input
-                } else {
-                    return Err(name.span.make_error("generic input not found"));
-                };
-                let resolved = transpile_generic_block_def(generic_input, &inputs_block_def_store)?;
-                let transpiled = transpile_block_def_by_block(&resolved, &inputs_block_def_store)?;
-                transpiled_store.insert(name.id.clone(), transpiled.clone());
+                let transpiled = output.inputs[&name.id].clone();
                 target.inputs.insert(name.id.clone(), transpiled);
-                target.order.push(DefType::Input(name.clone()));
             }
             DefType::Enum(name) => {
-                let transpiled = transpile_block_def_by_id(name, &enums_block_def_store)?;
+                let transpiled = output.enums[&name.id].clone();
                 target.enums.insert(name.id.clone(), transpiled);
-                target.order.push(DefType::Enum(name.clone()));
             }
             DefType::Interface(name) => {
-                let transpiled = transpile_block_def_by_id(name, &interfaces_block_def_store)?;
+                let transpiled = output.interfaces[&name.id].clone();
                 target.interfaces.insert(name.id.clone(), transpiled);
-                target.order.push(DefType::Interface(name.clone()));
             }
             DefType::Scalar(name) => {
-                let transpiled = spec.scalars.get(&name.id).unwrap();
-                target.scalars.insert(name.id.clone(), transpiled.clone());
-                target.order.push(DefType::Scalar(name.clone()));
+                let transpiled = output.scalars[&name.id].clone();
+                target.scalars.insert(name.id.clone(), transpiled);
             }
             DefType::Union(name) => {
-                let transpiled = spec.unions.get(&name.id).unwrap();
-                target.unions.insert(name.id.clone(), transpiled.clone());
-                target.order.push(DefType::Union(name.clone()));
+                let transpiled = output.unions[&name.id].clone();
+                target.unions.insert(name.id.clone(), transpiled);
             }
             DefType::Directive(name) => {
-                let transpiled = spec.directives.get(&name.id).unwrap();
-                target
-                    .directives
-                    .insert(name.id.clone(), transpiled.clone());
-                target.order.push(DefType::Directive(name.clone()));
+                let transpiled = output.directives[&name.id].clone();
+                target.directives.insert(name.id.clone(), transpiled);
             }
             DefType::Schema(name) => {
-                let transpiled = spec.schemas.get(name).unwrap();
-                target.schemas.insert(name.clone(), transpiled.clone());
-                target.order.push(DefType::Schema(name.clone()))
+                let transpiled = output.schemas[name].clone();
+                target.schemas.insert(name.clone(), transpiled);
+            }
+            DefType::GenericType(_)
+            | DefType::GenericInput(_)
+            | DefType::GenericInterface(_)
+            | DefType::GenericUnion(_)
+            | DefType::Modifier(_) => {
+                // they are transpiled into the definitions above
+            }
+        }
+        target.order.push(def.clone());
+    }
+}
+
+pub fn transpile_spec(spec: &Spec, options: &TranspileSpecOptions) -> Result<Spec, Box<dyn Error>> {
+    transpile_spec_with_cache(spec, options, &mut TranspileCache::new())
+}
+
+/// Transpiles a spec reusing the definitions of `cache` that are not affected by the
+/// changes made since it was last used, the rest are transpiled again.
+pub fn transpile_spec_with_cache(
+    spec: &Spec,
+    options: &TranspileSpecOptions,
+    cache: &mut TranspileCache,
+) -> Result<Spec, Box<dyn Error>> {
+    if cache.options.as_ref() != Some(options) {
+        cache.defs.clear();
+        cache.options = Some(options.clone());
+    }
+    let changed = changed_names(&cache.spec, spec);
+    let mut previous_defs = std::mem::take(&mut cache.defs);
+    let mut target = Spec::default();
+    let mut transpiled_store = HashMap::new();
+    let derived_defs = RefCell::new(vec![]);
+    let mut derived_names: HashMap<String, DerivedName> = HashMap::new();
+    // a definition that fails does not prevent the next ones from being transpiled, so
+    // that all the errors are reported at once
+    let mut errors = vec![];
+
+    for def in spec.order.iter() {
+        let key = def_key(def);
+        let previous = previous_defs
+            .remove(key)
+            .filter(|cached| cached.def == *def && cached.deps.is_disjoint(&changed));
+        if let Some(cached) = previous {
+            append(&mut target, &cached.output);
+            if let Some(stored) = &cached.stored {
+                transpiled_store.insert(key.to_string(), stored.clone());
+            }
+            derived_names.extend(cached.derived.iter().cloned());
+            cache.defs.insert(key.to_string(), cached);
+            continue;
+        }
+        cache.misses += 1;
+
+        let deps = RefCell::new(HashSet::from([key.to_string()]));
+        let mut output = Spec::default();
+        let mut derived = vec![];
+        let result = transpile_def(
+            def,
+            spec,
+            options,
+            &derived_defs,
+            &deps,
+            &mut transpiled_store,
+            &mut output,
+        );
+        if let Err(err) = result {
+            errors.push(err);
+        }
+
+        // definitions derived while transpiling the current one are placed right after it,
+        // they might derive other definitions themselves, so keep going until there are no more
+        while !derived_defs.borrow().is_empty() {
+            let generated = derived_defs.borrow_mut().remove(0);
+            let result = transpile_derived_def(
+                generated,
+                key,
+                spec,
+                options,
+                &derived_defs,
+                &deps,
+                &transpiled_store,
+                &mut derived_names,
+                &mut output,
+            );
+            match result {
+                Ok(Some(name)) => derived.push((name.clone(), derived_names[&name].clone())),
+                Ok(None) => {}
+                Err(err) => errors.push(err),
+            }
+        }
+
+        append(&mut target, &output);
+        // the ones it read that were transpiled before, or that derived the same
+        // definitions as it, bring their own dependencies
+        let mut deps = deps.into_inner();
+        for dep in deps.clone() {
+            let owner = derived_names.get(&dep).map_or(dep.as_str(), |d| &d.owner);
+            if let Some(cached) = cache.defs.get(owner) {
+                deps.extend(cached.deps.iter().cloned());
             }
         }
+        let stored = match def {
+            DefType::GenericType(_) | DefType::GenericInput(_) | DefType::GenericInterface(_) => {
+                transpiled_store.get(key).cloned()
+            }
+            _ => None,
+        };
+        cache.defs.insert(
+            key.to_string(),
+            CachedDef {
+                def: def.clone(),
+                output,
+                stored,
+                derived,
+                deps,
+            },
+        );
+    }
+    if !errors.is_empty() {
+        // the ones that failed, and the ones transpiled after them, might have turned out
+        // different without the failure, so nothing is reused next time
+        cache.defs.clear();
     }
+    cache.spec = spec.clone();
+    ErrorList::check(errors)?;
     Ok(target)
 }
+
+fn transpile_def(
+    def: &DefType,
+    spec: &Spec,
+    options: &TranspileSpecOptions,
+    derived_defs: &RefCell<Vec<GenericBlockDef>>,
+    deps: &RefCell<HashSet<String>>,
+    transpiled_store: &mut HashMap<String, BlockDef>,
+    target: &mut Spec,
+) -> Result<(), Box<dyn Error>> {
+    let types_block_def_store = BlockDefStore::from(vec![
+        &spec.types,
+        &transpiled_store,
+        &spec.interfaces,
+        &spec.inputs,
+    ])
+    .with_spec(spec)
+    .with_derived_defs(derived_defs)
+    .with_deps(deps)
+    .with_options(options);
+
+    let inputs_block_def_store =
+        BlockDefStore::from(vec![&spec.inputs, &transpiled_store, &spec.types])
+            .with_spec(spec)
+            .with_derived_defs(derived_defs)
+            .with_deps(deps)
+            .with_options(options);
+
+    let enums_block_def_store = BlockDefStore::from(&spec.enums).with_deps(deps);
+
+    let unions_block_def_store = BlockDefStore::from(vec![]).with_spec(spec).with_deps(deps);
+
+    let interfaces_block_def_store = BlockDefStore::from(vec![&spec.interfaces, &transpiled_store])
+        .with_spec(spec)
+        .with_derived_defs(derived_defs)
+        .with_deps(deps)
+        .with_options(options);
+
+    match def {
+        DefType::Type(name) => {
+            if name.id.starts_with(&options.private_prefix) {
+                return Ok(());
+            }
+            let transpiled = transpile_block_def_by_id(name, &types_block_def_store)?;
+            if transpiled.generic.is_none() {
+                target.types.insert(name.id.clone(), transpiled);
+                target.order.push(DefType::Type(name.clone()));
+            }
+        }
+        DefType::GenericType(name) => {
+            let generic_type = if let Some(generic_type) = spec.generic_types.get(&name.id) {
+                generic_type
+            } else {
+                return Err(name.span.make_error("generic type not found"));
+            };
+            let resolved = transpile_generic_block_def(generic_type, &types_block_def_store)?;
+            let transpiled = transpile_block_def_by_block(&resolved, &types_block_def_store)?;
+            transpiled_store.insert(name.id.clone(), transpiled.clone());
+            target.types.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Type(name.clone()));
+        }
+        DefType::Input(name) => {
+            if name.id.starts_with(&options.private_prefix) {
+                return Ok(());
+            }
+            let transpiled = transpile_block_def_by_id(name, &inputs_block_def_store)?;
+            if transpiled.generic.is_none() {
+                target.inputs.insert(name.id.clone(), transpiled);
+                target.order.push(DefType::Input(name.clone()));
+            }
+        }
+        DefType::GenericInput(name) => {
+            let generic_input = if let Some(generic_input) = spec.generic_inputs.get(&name.id) {
+                generic_input
+            } else {
+                return Err(name.span.make_error("generic input not found"));
+            };
+            let resolved = transpile_generic_block_def(generic_input, &inputs_block_def_store)?;
+            let transpiled = transpile_block_def_by_block(&resolved, &inputs_block_def_store)?;
+            transpiled_store.insert(name.id.clone(), transpiled.clone());
+            target.inputs.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Input(name.clone()));
+        }
+        DefType::Enum(name) => {
+            let transpiled = transpile_block_def_by_id(name, &enums_block_def_store)?;
+            target.enums.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Enum(name.clone()));
+        }
+        DefType::Interface(name) => {
+            let transpiled = transpile_block_def_by_id(name, &interfaces_block_def_store)?;
+            if transpiled.generic.is_none() {
+                target.interfaces.insert(name.id.clone(), transpiled);
+                target.order.push(DefType::Interface(name.clone()));
+            }
+        }
+        DefType::GenericInterface(name) => {
+            let generic_interface =
+                if let Some(generic_interface) = spec.generic_interfaces.get(&name.id) {
+                    generic_interface
+                } else {
+                    return Err(name.span.make_error("generic interface not found"));
+                };
+            let resolved =
+                transpile_generic_block_def(generic_interface, &interfaces_block_def_store)?;
+            let transpiled = transpile_block_def_by_block(&resolved, &interfaces_block_def_store)?;
+            transpiled_store.insert(name.id.clone(), transpiled.clone());
+            target.interfaces.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Interface(name.clone()));
+        }
+        DefType::Scalar(name) => {
+            let transpiled = spec.scalars.get(&name.id).unwrap();
+            target.scalars.insert(name.id.clone(), transpiled.clone());
+            target.order.push(DefType::Scalar(name.clone()));
+        }
+        DefType::Union(name) => {
+            let transpiled = spec.unions.get(&name.id).unwrap();
+            if transpiled.generic.is_none() {
+                target.unions.insert(name.id.clone(), transpiled.clone());
+                target.order.push(DefType::Union(name.clone()));
+            }
+        }
+        DefType::GenericUnion(name) => {
+            let generic_union = if let Some(generic_union) = spec.generic_unions.get(&name.id) {
+                generic_union
+            } else {
+                return Err(name.span.make_error("generic union not found"));
+            };
+            let transpiled = transpile_generic_union(generic_union, &unions_block_def_store)?;
+            target.unions.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Union(name.clone()));
+        }
+        DefType::Directive(name) => {
+            let transpiled = spec.directives.get(&name.id).unwrap();
+            target
+                .directives
+                .insert(name.id.clone(), transpiled.clone());
+            target.order.push(DefType::Directive(name.clone()));
+        }
+        DefType::Modifier(_) => {
+            // modifiers are applied where they are used, there is nothing to transpile
+        }
+        DefType::Schema(name) => {
+            let transpiled = spec.schemas.get(name).unwrap();
+            target.schemas.insert(name.clone(), transpiled.clone());
+            target.order.push(DefType::Schema(name.clone()))
+        }
+    }
+
+    Ok(())
+}
+
+/// Transpiles a definition derived by `owner`, returning its name unless it was already
+/// generated.
+#[allow(clippy::too_many_arguments)]
+fn transpile_derived_def(
+    derived: GenericBlockDef,
+    owner: &str,
+    spec: &Spec,
+    options: &TranspileSpecOptions,
+    derived_defs: &RefCell<Vec<GenericBlockDef>>,
+    deps: &RefCell<HashSet<String>>,
+    transpiled_store: &HashMap<String, BlockDef>,
+    derived_names: &mut HashMap<String, DerivedName>,
+    target: &mut Spec,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let types_block_def_store = BlockDefStore::from(vec![
+        &spec.types,
+        &transpiled_store,
+        &spec.interfaces,
+        &spec.inputs,
+    ])
+    .with_spec(spec)
+    .with_derived_defs(derived_defs)
+    .with_deps(deps)
+    .with_options(options);
+    let inputs_block_def_store =
+        BlockDefStore::from(vec![&spec.inputs, &transpiled_store, &spec.types])
+            .with_spec(spec)
+            .with_derived_defs(derived_defs)
+            .with_deps(deps)
+            .with_options(options);
+    let interfaces_block_def_store = BlockDefStore::from(vec![&spec.interfaces, &transpiled_store])
+        .with_spec(spec)
+        .with_derived_defs(derived_defs)
+        .with_deps(deps)
+        .with_options(options);
+    let name = derived.name.clone();
+    let signature = derived_signature(&derived);
+    // whether it is generated here depends on the definitions declared and derived so far
+    deps.borrow_mut().insert(name.id.clone());
+    if let Some(existing) = derived_names.get(&name.id) {
+        if let (Some(existing), Some(signature)) = (&existing.signature, &signature) {
+            if existing != signature {
+                return Err(name.span.make_error(&format!(
+                    "{signature} and {existing} would both generate {}, use a named definition for one of them",
+                    name.id
+                )));
+            }
+        }
+        return Ok(None);
+    }
+    if is_declared_as(spec, &derived) {
+        // the declared one is generated on its own
+        return Ok(None);
+    }
+    if types_block_def_store.get_kind(&name.id).is_some() {
+        let err = name.span.make_error(&format!(
+            "cannot derive {} because it is already defined",
+            name.id
+        ));
+        return Err(match spec.definition(&name.id) {
+            Some(first) => err.with_label(first.span.make_label("defined here")),
+            None => err,
+        });
+    }
+    derived_names.insert(
+        name.id.clone(),
+        DerivedName {
+            signature,
+            owner: owner.to_string(),
+        },
+    );
+    let store = match derived.kind {
+        BlockDefType::Input => &inputs_block_def_store,
+        BlockDefType::Interface => &interfaces_block_def_store,
+        _ => &types_block_def_store,
+    };
+    let pending = derived_defs.borrow().len();
+    let resolved = transpile_generic_block_def(&derived, store)?;
+    let transpiled = transpile_block_def_by_block(&resolved, store)?;
+    {
+        // the ones derived by this derived definition go right after it
+        let mut derived_defs = derived_defs.borrow_mut();
+        let nested: Vec<_> = derived_defs.drain(pending..).collect();
+        derived_defs.splice(0..0, nested);
+    }
+    let generated = name.id.clone();
+    match derived.kind {
+        BlockDefType::Input => {
+            target.inputs.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Input(name));
+        }
+        BlockDefType::Interface => {
+            target.interfaces.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Interface(name));
+        }
+        _ => {
+            target.types.insert(name.id.clone(), transpiled);
+            target.order.push(DefType::Type(name));
+        }
+    }
+    Ok(Some(generated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqxl_parser::{parse_spec_vfs, MemoryFileSystem};
+
+    const BASE: &str = "type _Base { id: ID! }";
+
+    const SCHEMA: &str = r#"
+import "base"
+
+type User { ..._Base name: String }
+type Post { title: String }
+type Page<T> { items: [T!]! }
+type UserPage = Page<User>
+input Address { street: String! }
+input UserInput { name: String!, address: Address! }
+input UserPatch = DeepOptional<UserInput>
+type Query { users: UserPage, posts: [Post!]! }
+"#;
+
+    #[test]
+    fn test_only_transpiles_affected_definitions_again() {
+        let vfs = MemoryFileSystem::from([("/main.graphqxl", SCHEMA), ("/base.graphqxl", BASE)]);
+        let options = TranspileSpecOptions {
+            private_prefix: "_".to_string(),
+            ..Default::default()
+        };
+        let mut cache = TranspileCache::new();
+        let transpile = |cache: &mut TranspileCache| {
+            let spec = parse_spec_vfs("/main.graphqxl", &vfs).unwrap();
+            let transpiled = transpile_spec_with_cache(&spec, &options, cache).unwrap();
+            let fresh = transpile_spec(&spec, &options).unwrap();
+            assert_eq!(transpiled, fresh);
+            for (name, block_def) in fresh.types.iter() {
+                assert!(same_place(&transpiled.types[name].span, &block_def.span));
+            }
+            for (name, block_def) in fresh.inputs.iter() {
+                assert!(same_place(&transpiled.inputs[name].span, &block_def.span));
+            }
+            transpiled
+        };
+
+        transpile(&mut cache);
+        assert_eq!(cache.misses(), 9);
+        transpile(&mut cache);
+        assert_eq!(cache.misses(), 9);
+
+        // only the ones that spread it are affected
+        vfs.insert(
+            "/base.graphqxl",
+            "type _Base { id: ID!, createdAt: String }",
+        );
+        let transpiled = transpile(&mut cache);
+        assert_eq!(cache.misses(), 11);
+        assert_eq!(transpiled.types["User"].entries.len(), 3);
+
+        // the derived definitions are generated again when the ones they come from change,
+        // the definitions placed after a change in the same file move, so they are affected too
+        vfs.insert(
+            "/main.graphqxl",
+            &SCHEMA.replace("{ street: String! }", "{ street: String!, city: String! }"),
+        );
+        let transpiled = transpile(&mut cache);
+        assert_eq!(cache.misses(), 15);
+        assert_eq!(transpiled.inputs["AddressPatch"].entries.len(), 2);
+    }
+}
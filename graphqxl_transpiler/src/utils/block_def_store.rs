@@ -1,26 +1,96 @@
-use graphqxl_parser::BlockDef;
-use std::collections::HashMap;
+use crate::TranspileSpecOptions;
+use graphqxl_parser::{
+    BlockDef, BlockDefType, GenericBlockDef, GenericKind, Implements, ModifierDef, Spec, Union,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+const BUILT_IN_SCALARS: [&str; 5] = ["Int", "Float", "String", "Boolean", "ID"];
+pub(crate) const DEFAULT_INLINE_GENERIC_NAME: &str = "{args}{name}";
 
 pub(crate) struct BlockDefStore<'a> {
     hash_maps: Vec<&'a HashMap<String, BlockDef>>,
+    spec: Option<&'a Spec>,
+    derived_defs: Option<&'a RefCell<Vec<GenericBlockDef>>>,
+    options: Option<&'a TranspileSpecOptions>,
+    deps: Option<&'a RefCell<HashSet<String>>>,
 }
 
 impl<'a> From<&'a HashMap<String, BlockDef>> for BlockDefStore<'a> {
     fn from(value: &'a HashMap<String, BlockDef>) -> Self {
         Self {
             hash_maps: vec![value],
+            spec: None,
+            derived_defs: None,
+            options: None,
+            deps: None,
         }
     }
 }
 
 impl<'a> From<Vec<&'a HashMap<String, BlockDef>>> for BlockDefStore<'a> {
     fn from(value: Vec<&'a HashMap<String, BlockDef>>) -> Self {
-        Self { hash_maps: value }
+        Self {
+            hash_maps: value,
+            spec: None,
+            derived_defs: None,
+            options: None,
+            deps: None,
+        }
     }
 }
 
 impl<'a> BlockDefStore<'a> {
+    pub(crate) fn with_spec(mut self, spec: &'a Spec) -> Self {
+        self.spec = Some(spec);
+        self
+    }
+
+    pub(crate) fn with_derived_defs(
+        mut self,
+        derived_defs: &'a RefCell<Vec<GenericBlockDef>>,
+    ) -> Self {
+        self.derived_defs = Some(derived_defs);
+        self
+    }
+
+    pub(crate) fn with_options(mut self, options: &'a TranspileSpecOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Collects the names of every definition that is looked up, so that the result can
+    /// be reused until one of them changes.
+    pub(crate) fn with_deps(mut self, deps: &'a RefCell<HashSet<String>>) -> Self {
+        self.deps = Some(deps);
+        self
+    }
+
+    fn depend_on(&self, key: &str) {
+        if let Some(deps) = self.deps {
+            deps.borrow_mut().insert(key.to_string());
+        }
+    }
+
+    /// The pattern used for naming the definitions generated by inline generic instantiations.
+    pub(crate) fn inline_generic_name(&self) -> &str {
+        match self.options {
+            Some(options) => &options.inline_generic_name,
+            None => DEFAULT_INLINE_GENERIC_NAME,
+        }
+    }
+
+    /// The name without the private prefix, used for the definitions generated out of
+    /// private ones, which are still referenced from public definitions.
+    pub(crate) fn public_name<'b>(&self, name: &'b str) -> &'b str {
+        match self.options {
+            Some(options) => name.strip_prefix(&options.private_prefix).unwrap_or(name),
+            None => name,
+        }
+    }
+
     pub(crate) fn get(&self, key: &str) -> Option<&BlockDef> {
+        self.depend_on(key);
         for hash_map in self.hash_maps.iter() {
             if let Some(result) = hash_map.get(key) {
                 return Some(result);
@@ -28,4 +98,65 @@ impl<'a> BlockDefStore<'a> {
         }
         None
     }
+
+    pub(crate) fn get_modifier(&self, key: &str) -> Option<&ModifierDef> {
+        self.depend_on(key);
+        self.spec?.modifiers.get(key)
+    }
+
+    pub(crate) fn get_union(&self, key: &str) -> Option<&Union> {
+        self.depend_on(key);
+        self.spec?.unions.get(key)
+    }
+
+    /// Finds out which kind of definition a name refers to, looking also at
+    /// the definitions that are not stored as block definitions.
+    pub(crate) fn get_kind(&self, key: &str) -> Option<GenericKind> {
+        if BUILT_IN_SCALARS.contains(&key) {
+            return Some(GenericKind::Scalar);
+        }
+        self.depend_on(key);
+        if let Some(spec) = self.spec {
+            if spec.types.contains_key(key) || spec.generic_types.contains_key(key) {
+                return Some(GenericKind::Type);
+            } else if spec.inputs.contains_key(key) || spec.generic_inputs.contains_key(key) {
+                return Some(GenericKind::Input);
+            } else if spec.interfaces.contains_key(key) || spec.generic_interfaces.contains_key(key)
+            {
+                return Some(GenericKind::Interface);
+            } else if spec.unions.contains_key(key) || spec.generic_unions.contains_key(key) {
+                return Some(GenericKind::Union);
+            } else if spec.enums.contains_key(key) {
+                return Some(GenericKind::Enum);
+            } else if spec.scalars.contains_key(key) {
+                return Some(GenericKind::Scalar);
+            }
+        }
+        self.get(key).map(|block_def| match block_def.kind {
+            BlockDefType::Type => GenericKind::Type,
+            BlockDefType::Input => GenericKind::Input,
+            BlockDefType::Enum => GenericKind::Enum,
+            BlockDefType::Interface => GenericKind::Interface,
+        })
+    }
+
+    pub(crate) fn get_implements(&self, key: &str) -> Option<&Implements> {
+        self.depend_on(key);
+        let block_def = match self.get(key) {
+            Some(block_def) => Some(block_def),
+            None => self
+                .spec
+                .and_then(|spec| spec.types.get(key).or_else(|| spec.interfaces.get(key))),
+        };
+        block_def?.implements.as_ref()
+    }
+
+    /// Registers a new definition that needs to be generated as a consequence of resolving
+    /// a reference, for example, the derived copies of nested inputs made by deep modifiers.
+    /// Repeated definitions are deduplicated by whoever generates them.
+    pub(crate) fn derive(&self, generic_block_def: GenericBlockDef) {
+        if let Some(derived_defs) = self.derived_defs {
+            derived_defs.borrow_mut().push(generic_block_def);
+        }
+    }
 }
@@ -0,0 +1,165 @@
+use crate::utils::{BlockDefStore, GenericMap};
+use graphqxl_parser::{
+    Generic, GenericConstraint, GenericConstraintItem, GenericKind, ValueBasicType, ValueType,
+};
+use std::error::Error;
+
+fn satisfies(
+    name: &str,
+    kind: &GenericKind,
+    constraint: &GenericConstraint,
+    store: &BlockDefStore,
+) -> bool {
+    constraint.items.iter().any(|item| match item {
+        GenericConstraintItem::Kind(constraint_kind) => constraint_kind == kind,
+        GenericConstraintItem::Implements(implements) => match store.get_implements(name) {
+            Some(implemented) => implements.interfaces.iter().all(|interface| {
+                implemented
+                    .interfaces
+                    .iter()
+                    .any(|implemented| implemented.id == interface.id)
+            }),
+            None => false,
+        },
+    })
+}
+
+fn check_generic_constraint(
+    param: &str,
+    value_type: &ValueType,
+    constraint: &GenericConstraint,
+    store: &BlockDefStore,
+) -> Result<(), Box<dyn Error>> {
+    let basic_type = value_type.retrieve_basic_type();
+    let name = match basic_type {
+        ValueBasicType::Object(identifier) => identifier.id.as_str(),
+        ValueBasicType::GenericObject(expandable_ref) => expandable_ref.identifier.id.as_str(),
+        _ => "",
+    };
+    let kind = match basic_type {
+        ValueBasicType::Object(_) | ValueBasicType::GenericObject(_) => {
+            match store.get_kind(name) {
+                Some(kind) => kind,
+                None => {
+                    return Err(value_type
+                        .span()
+                        .make_error(&format!("{name} is undefined")))
+                }
+            }
+        }
+        _ => GenericKind::Scalar,
+    };
+    if satisfies(name, &kind, constraint, store) {
+        Ok(())
+    } else {
+        Err(value_type.span().make_error(&format!(
+            "{basic_type} does not satisfy the constraint of generic parameter {param}: {constraint}"
+        )))
+    }
+}
+
+/// Checks that the arguments bound to the generic parameters satisfy their constraints.
+pub(crate) fn check_generic_constraints(
+    generic: Option<&Generic>,
+    generic_map: &GenericMap,
+    store: &BlockDefStore,
+) -> Result<(), Box<dyn Error>> {
+    let generic = match generic {
+        Some(generic) => generic,
+        None => return Ok(()),
+    };
+    for arg in generic.args.iter() {
+        if let (Some(constraint), Some(value_type)) =
+            (generic.constraints.get(&arg.id), generic_map.get(&arg.id))
+        {
+            check_generic_constraint(&arg.id, value_type, constraint, store)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqxl_parser::{BlockDef, Identifier, Implements};
+    use std::collections::HashMap;
+
+    fn types() -> HashMap<String, BlockDef> {
+        HashMap::from([
+            (
+                "User".to_string(),
+                BlockDef::type_def("User").implements(Implements::from("Node")),
+            ),
+            ("Product".to_string(), BlockDef::type_def("Product")),
+            ("Status".to_string(), BlockDef::enum_def("Status")),
+        ])
+    }
+
+    fn check(constraint: GenericConstraint, value_type: ValueType) -> Result<(), Box<dyn Error>> {
+        let types = types();
+        let store = BlockDefStore::from(&types);
+        let t = "T".to_string();
+        let generic = Generic::from("T").constraint("T", constraint);
+        let generic_map = HashMap::from([(&t, &value_type)]);
+        check_generic_constraints(Some(&generic), &generic_map, &store)
+    }
+
+    #[test]
+    fn test_built_in_scalars_are_scalars() {
+        check(
+            GenericConstraint::build().kind(GenericKind::Scalar),
+            ValueType::string().non_nullable(),
+        )
+        .unwrap();
+        check(
+            GenericConstraint::build().kind(GenericKind::Scalar),
+            ValueType::object(Identifier::from("ID")),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_checks_any_of_the_kinds() {
+        check(
+            GenericConstraint::build()
+                .kind(GenericKind::Scalar)
+                .kind(GenericKind::Enum),
+            ValueType::object(Identifier::from("Status")),
+        )
+        .unwrap();
+        let err = check(
+            GenericConstraint::build()
+                .kind(GenericKind::Scalar)
+                .kind(GenericKind::Enum),
+            ValueType::object(Identifier::from("User")),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "User does not satisfy the constraint of generic parameter T: scalar | enum"
+        ));
+    }
+
+    #[test]
+    fn test_checks_implements() {
+        check(
+            GenericConstraint::build().implements(Implements::from("Node")),
+            ValueType::object(Identifier::from("User")),
+        )
+        .unwrap();
+        check(
+            GenericConstraint::build().implements(Implements::from("Node")),
+            ValueType::object(Identifier::from("Product")),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_fails_with_undefined_types() {
+        let err = check(
+            GenericConstraint::build().kind(GenericKind::Type),
+            ValueType::object(Identifier::from("Undefined")),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Undefined is undefined"));
+    }
+}
@@ -0,0 +1,74 @@
+use graphqxl_parser::{LocatedError, OwnedSpan};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A block, or one of the spreads that were expanded inside it, along with where it is.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExpansionStep {
+    pub(crate) name: String,
+    pub(crate) span: OwnedSpan,
+}
+
+/// How a definition was reached, starting from the block being transpiled and going
+/// through every spread that was expanded until getting there, like
+/// `type A -> ...B -> ...C<Int>`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct ExpansionPath(Vec<ExpansionStep>);
+
+impl ExpansionPath {
+    pub(crate) fn step(&self, name: &str, span: &OwnedSpan) -> Self {
+        let mut clone = self.clone();
+        clone.0.push(ExpansionStep {
+            name: name.to_string(),
+            span: span.clone(),
+        });
+        clone
+    }
+
+    /// The first spread expanded inside the block being transpiled.
+    pub(crate) fn outermost(&self) -> Option<&ExpansionStep> {
+        self.0.get(1)
+    }
+
+    /// Whether a spread was expanded inside another one, which is when it is no longer
+    /// obvious how something got to the block being transpiled.
+    pub(crate) fn is_nested(&self) -> bool {
+        self.0.len() > 2
+    }
+
+    /// Points to the spreads that were expanded, skipping the ones that contain the place
+    /// the error already points to.
+    pub(crate) fn label_spreads(&self, mut err: Box<LocatedError>) -> Box<LocatedError> {
+        let (start, end) = err.offsets();
+        for step in self.0.iter().skip(1) {
+            let span = &step.span;
+            if span.file == err.file && span.start <= start && end <= span.end {
+                continue;
+            }
+            err = err.with_label(span.make_label("in this expansion"));
+        }
+        err
+    }
+
+    /// Adds to the error the spreads it was found through, errors that are not found
+    /// inside nested spreads are returned as they are.
+    pub(crate) fn trace(&self, err: Box<dyn Error>) -> Box<dyn Error> {
+        if !self.is_nested() {
+            return err;
+        }
+        match err.downcast::<LocatedError>() {
+            Ok(err) => {
+                let err = self.label_spreads(err);
+                err.with_note(&format!("expansion path: {self}"))
+            }
+            Err(err) => err,
+        }
+    }
+}
+
+impl Display for ExpansionPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let steps: Vec<&str> = self.0.iter().map(|step| step.name.as_str()).collect();
+        write!(f, "{}", steps.join(" -> "))
+    }
+}
@@ -1,3 +1,9 @@
 mod block_def_store;
+mod check_generic_constraints;
+mod expansion_path;
+mod replace_generic_args;
 
-pub(crate) use block_def_store::*;
\ No newline at end of file
+pub(crate) use block_def_store::*;
+pub(crate) use check_generic_constraints::*;
+pub(crate) use expansion_path::*;
+pub(crate) use replace_generic_args::*;
@@ -0,0 +1,371 @@
+use graphqxl_parser::{
+    Argument, ArgumentDefaultValue, BlockField, Directive, ExpandableRef, Generic, ModifiedRef,
+    ValueBasicData, ValueBasicType, ValueData, ValueType,
+};
+use std::collections::HashMap;
+use std::error::Error;
+
+pub(crate) type GenericMap<'a> = HashMap<&'a String, &'a ValueType>;
+
+/// Binds each generic parameter to the argument passed in the same position, or to its
+/// default value if no argument was passed for it.
+pub(crate) fn build_generic_map<'a>(
+    generic: Option<&'a Generic>,
+    expandable_ref: &'a ExpandableRef,
+) -> Result<GenericMap<'a>, Box<dyn Error>> {
+    let name = &expandable_ref.identifier.id;
+    let generic_call_args = match &expandable_ref.generic_call {
+        Some(generic_call) => generic_call.args.as_slice(),
+        None => &[],
+    };
+    let generic = match generic {
+        Some(generic) => generic,
+        None if generic_call_args.is_empty() => return Ok(HashMap::new()),
+        None => {
+            return Err(expandable_ref.span.make_error(&format!(
+                "Instantiated {name} with {} args, but it does not have generic parameters",
+                generic_call_args.len()
+            )))
+        }
+    };
+
+    if generic_call_args.len() > generic.args.len() {
+        return Err(expandable_ref.span.make_error(&format!(
+            "Instantiated {name} with {} args, expected {name}{generic}",
+            generic_call_args.len()
+        )));
+    }
+
+    let mut generic_map = HashMap::new();
+    let mut unbound = vec![];
+    for (i, arg) in generic.args.iter().enumerate() {
+        if let Some(generic_call_arg) = generic_call_args.get(i) {
+            generic_map.insert(&arg.id, generic_call_arg);
+        } else if let Some(default) = generic.defaults.get(&arg.id) {
+            generic_map.insert(&arg.id, default);
+        } else {
+            unbound.push(arg.id.as_str());
+        }
+    }
+
+    if !unbound.is_empty() {
+        let (noun, verb) = if unbound.len() == 1 {
+            ("Generic parameter", "is")
+        } else {
+            ("Generic parameters", "are")
+        };
+        return Err(expandable_ref.span.make_error(&format!(
+            "{noun} {} of {name} {verb} left unbound, expected {name}{generic}",
+            unbound.join(", "),
+        )));
+    }
+    Ok(generic_map)
+}
+
+pub(crate) fn replace_generic_value_type(
+    value_type: &mut ValueType,
+    generic_map: &GenericMap,
+) -> Result<(), Box<dyn Error>> {
+    match value_type.retrieve_basic_type() {
+        ValueBasicType::Object(object) => {
+            if let Some(replacement) = generic_map.get(&object.id) {
+                value_type.replace_basic_type((*replacement).clone())?;
+            }
+        }
+        ValueBasicType::GenericObject(expandable_ref) => {
+            // generic parameters can also be passed along to inline instantiations
+            let mut expandable_ref = expandable_ref.clone();
+            if let Some(generic_call) = &mut expandable_ref.generic_call {
+                for arg in generic_call.args.iter_mut() {
+                    replace_generic_value_type(arg, generic_map)?;
+                }
+            }
+            let span = expandable_ref.span.clone();
+            value_type.replace_basic_type(ValueType::Basic(
+                ValueBasicType::GenericObject(expandable_ref),
+                span,
+            ))?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn replace_generic_value_data(
+    value_data: &mut ValueData,
+    generic_map: &GenericMap,
+) -> Result<(), Box<dyn Error>> {
+    match value_data {
+        // a generic parameter passed to a directive is written as a bare name, which is
+        // replaced by the name of the type it is bound to
+        ValueData::Basic(ValueBasicData::Enum(name)) => {
+            if let Some(replacement) = generic_map.get(name) {
+                match replacement.retrieve_basic_type() {
+                    ValueBasicType::GenericObject(_) => {
+                        return Err(replacement.span().make_error(&format!(
+                            "generic parameter {name} is used in a directive, so it can only be bound to a named type"
+                        )))
+                    }
+                    basic_type => *name = basic_type.to_string(),
+                }
+            }
+        }
+        ValueData::Basic(_) => {}
+        ValueData::List(list) => {
+            for value_data in list.iter_mut() {
+                replace_generic_value_data(value_data, generic_map)?;
+            }
+        }
+        ValueData::Object(object) => {
+            for value_data in object.values_mut() {
+                replace_generic_value_data(value_data, generic_map)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the generic parameters passed as inputs of a directive call.
+pub(crate) fn replace_generic_directive(
+    directive: &mut Directive,
+    generic_map: &GenericMap,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(call) = &mut directive.call {
+        for input in call.inputs.iter_mut() {
+            replace_generic_value_data(&mut input.value, generic_map)?;
+        }
+    }
+    Ok(())
+}
+
+fn replace_generic_argument(
+    argument: &mut Argument,
+    generic_map: &GenericMap,
+) -> Result<(), Box<dyn Error>> {
+    replace_generic_value_type(&mut argument.value_type, generic_map)?;
+    for directive in argument.directives.iter_mut() {
+        replace_generic_directive(directive, generic_map)?;
+    }
+    if let ArgumentDefaultValue::Identifier(identifier) = &argument.default {
+        if let Some(replacement) = generic_map.get(&identifier.id) {
+            // a default value can only be an identifier if it is an enum value
+            if let ValueType::Basic(ValueBasicType::Object(value), _) = replacement {
+                argument.default = ArgumentDefaultValue::Identifier(value.clone());
+            } else {
+                return Err(replacement.span().make_error(&format!(
+                    "generic parameter {} is used as a default value, so it can only be bound to an enum value",
+                    identifier.id
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn replace_generic_block_field(
+    block_field: &mut BlockField,
+    generic_map: &GenericMap,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(value_type) = &mut block_field.value_type {
+        replace_generic_value_type(value_type, generic_map)?;
+    }
+    for argument in block_field.args.iter_mut() {
+        replace_generic_argument(argument, generic_map)?;
+    }
+    for directive in block_field.directives.iter_mut() {
+        replace_generic_directive(directive, generic_map)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn replace_generic_modified_ref(
+    modified_ref: &ModifiedRef,
+    generic_map: &GenericMap,
+) -> Result<ModifiedRef, Box<dyn Error>> {
+    Ok(match modified_ref {
+        ModifiedRef::Required(inner, span) => ModifiedRef::Required(
+            Box::new(replace_generic_modified_ref(inner, generic_map)?),
+            span.clone(),
+        ),
+        ModifiedRef::Optional(inner, span) => ModifiedRef::Optional(
+            Box::new(replace_generic_modified_ref(inner, generic_map)?),
+            span.clone(),
+        ),
+        ModifiedRef::DeepRequired(inner, span) => ModifiedRef::DeepRequired(
+            Box::new(replace_generic_modified_ref(inner, generic_map)?),
+            span.clone(),
+        ),
+        ModifiedRef::DeepOptional(inner, span) => ModifiedRef::DeepOptional(
+            Box::new(replace_generic_modified_ref(inner, generic_map)?),
+            span.clone(),
+        ),
+        ModifiedRef::Pick(inner, fields, span) => ModifiedRef::Pick(
+            Box::new(replace_generic_modified_ref(inner, generic_map)?),
+            fields.clone(),
+            span.clone(),
+        ),
+        ModifiedRef::Omit(inner, fields, span) => ModifiedRef::Omit(
+            Box::new(replace_generic_modified_ref(inner, generic_map)?),
+            fields.clone(),
+            span.clone(),
+        ),
+        ModifiedRef::ExpandableRef(expandable_ref) => {
+            let mut expandable_ref = expandable_ref.clone();
+            if let Some(generic_call) = &mut expandable_ref.generic_call {
+                for arg in generic_call.args.iter_mut() {
+                    replace_generic_value_type(arg, generic_map)?;
+                }
+            } else if let Some(replacement) = generic_map.get(&expandable_ref.identifier.id) {
+                // the generic parameter is being expanded itself, so it must be a type or an input
+                if let ValueType::Basic(ValueBasicType::Object(identifier), span) = replacement {
+                    expandable_ref = Box::new(ExpandableRef {
+                        span: span.clone(),
+                        identifier: identifier.clone(),
+                        generic_call: None,
+                    });
+                } else {
+                    return Err(replacement.span().make_error(&format!(
+                        "generic parameter {} is expanded, so it can only be bound to a type or an input",
+                        expandable_ref.identifier.id
+                    )));
+                }
+            }
+            ModifiedRef::ExpandableRef(expandable_ref)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqxl_parser::Identifier;
+
+    #[test]
+    fn test_binds_default_values() {
+        let generic = Generic::from("T").arg_with_default("C", ValueType::string().non_nullable());
+        let expandable_ref = ExpandableRef::from("Page").generic_arg(ValueType::int());
+        let generic_map = build_generic_map(Some(&generic), &expandable_ref).unwrap();
+        assert_eq!(generic_map.get(&"T".to_string()), Some(&&ValueType::int()));
+        assert_eq!(
+            generic_map.get(&"C".to_string()),
+            Some(&&ValueType::string().non_nullable())
+        );
+    }
+
+    #[test]
+    fn test_lists_expected_parameters_when_one_is_unbound() {
+        let generic = Generic::from("T")
+            .arg("F")
+            .arg_with_default("C", ValueType::string().non_nullable());
+        let expandable_ref = ExpandableRef::from("Page").generic_arg(ValueType::int());
+        let err = build_generic_map(Some(&generic), &expandable_ref).unwrap_err();
+        assert!(err.to_string().contains(
+            "Generic parameter F of Page is left unbound, expected Page<T, F, C = String!>"
+        ))
+    }
+
+    #[test]
+    fn test_lists_expected_parameters_when_there_are_too_many_args() {
+        let generic = Generic::from("T");
+        let expandable_ref = ExpandableRef::from("Page")
+            .generic_arg(ValueType::int())
+            .generic_arg(ValueType::int());
+        let err = build_generic_map(Some(&generic), &expandable_ref).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Instantiated Page with 2 args, expected Page<T>"))
+    }
+
+    #[test]
+    fn test_replaces_field_type_and_argument_types() {
+        let t = "T".to_string();
+        let f = "F".to_string();
+        let user = ValueType::object(Identifier::from("User"));
+        let filter = ValueType::object(Identifier::from("Filter")).non_nullable();
+        let generic_map = HashMap::from([(&t, &user), (&f, &filter)]);
+        let mut block_field = BlockField::build("items")
+            .value_type(
+                ValueType::object(Identifier::from("T"))
+                    .non_nullable()
+                    .array(),
+            )
+            .arg(Argument::object("filter", Identifier::from("F")));
+        replace_generic_block_field(&mut block_field, &generic_map).unwrap();
+        assert_eq!(
+            block_field,
+            BlockField::build("items")
+                .value_type(
+                    ValueType::object(Identifier::from("User"))
+                        .non_nullable()
+                        .array()
+                )
+                .arg(Argument::build(
+                    "filter",
+                    ValueType::object(Identifier::from("Filter")).non_nullable()
+                ))
+        )
+    }
+
+    #[test]
+    fn test_replaces_inside_inline_instantiations() {
+        let t = "T".to_string();
+        let user = ValueType::object(Identifier::from("User"));
+        let generic_map = HashMap::from([(&t, &user)]);
+        let mut value_type = ValueType::generic_object(
+            ExpandableRef::from("Edge").generic_arg(ValueType::object(Identifier::from("T"))),
+        )
+        .non_nullable();
+        replace_generic_value_type(&mut value_type, &generic_map).unwrap();
+        assert_eq!(
+            value_type,
+            ValueType::generic_object(ExpandableRef::from("Edge").generic_arg(user.clone()))
+                .non_nullable()
+        )
+    }
+
+    #[test]
+    fn test_replaces_argument_default_identifier() {
+        let o = "O".to_string();
+        let asc = ValueType::object(Identifier::from("ASC"));
+        let generic_map = HashMap::from([(&o, &asc)]);
+        let mut argument = Argument::object("order", Identifier::from("Order"))
+            .default(ArgumentDefaultValue::Identifier(Identifier::from("O")));
+        replace_generic_argument(&mut argument, &generic_map).unwrap();
+        assert_eq!(
+            argument.default,
+            ArgumentDefaultValue::Identifier(Identifier::from("ASC"))
+        )
+    }
+
+    #[test]
+    fn test_replaces_directive_inputs() {
+        let t = "T".to_string();
+        let user = ValueType::object(Identifier::from("User")).non_nullable();
+        let generic_map = HashMap::from([(&t, &user)]);
+        let key = |value: &str| {
+            Directive::build("key").input(
+                "type",
+                ValueData::Basic(ValueBasicData::Enum(value.to_string())),
+            )
+        };
+        let mut block_field = BlockField::build("items")
+            .value_type(ValueType::string())
+            .directive(key("T"));
+        replace_generic_block_field(&mut block_field, &generic_map).unwrap();
+        assert_eq!(block_field.directives, vec![key("User")]);
+
+        let page = ValueType::generic_object(ExpandableRef::from("Page").generic_arg(user.clone()));
+        let generic_map = HashMap::from([(&t, &page)]);
+        replace_generic_directive(&mut key("T"), &generic_map).unwrap_err();
+    }
+
+    #[test]
+    fn test_fails_if_default_identifier_is_not_bound_to_an_enum_value() {
+        let o = "O".to_string();
+        let int = ValueType::int();
+        let generic_map = HashMap::from([(&o, &int)]);
+        let mut argument = Argument::object("order", Identifier::from("Order"))
+            .default(ArgumentDefaultValue::Identifier(Identifier::from("O")));
+        replace_generic_argument(&mut argument, &generic_map).unwrap_err();
+    }
+}
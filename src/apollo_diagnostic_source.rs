@@ -1,82 +1,148 @@
-use anyhow::{anyhow, Result};
-use apollo_compiler::{diagnostics::DiagnosticData, ApolloDiagnostic};
-use graphqxl_synthesizer::SourceMapEntry;
+use crate::diagnostic::{Diagnostic, DiagnosticError};
+use apollo_compiler::diagnostics::{DiagnosticData, DiagnosticLocation};
+use apollo_compiler::ApolloDiagnostic;
+use graphqxl_synthesizer::SourceMap;
 
-pub(crate) fn is_fatal_diagnostic(diagnostic: &ApolloDiagnostic) -> bool {
-    match *diagnostic.data {
-        DiagnosticData::SyntaxError { .. } => true,
-        DiagnosticData::UniqueDefinition { .. } => true,
-        DiagnosticData::UniqueArgument { .. } => true,
-        DiagnosticData::UniqueInputValue { .. } => true,
-        DiagnosticData::UniqueEnumValue { .. } => true,
-        DiagnosticData::RecursiveDirectiveDefinition { .. } => true,
-        DiagnosticData::RecursiveInterfaceDefinition { .. } => true,
-        DiagnosticData::RecursiveInputObjectDefinition { .. } => true,
-        DiagnosticData::RecursiveFragmentDefinition { .. } => true,
-        DiagnosticData::DuplicateImplementsInterface { .. } => true,
-        DiagnosticData::TransitiveImplementedInterfaces { .. } => true,
-        DiagnosticData::OutputType { .. } => true,
-        DiagnosticData::InputType { .. } => true,
-        DiagnosticData::MissingField { .. } => true,
-        DiagnosticData::UndefinedField { .. } => true,
-        DiagnosticData::UndefinedArgument { .. } => true,
-        DiagnosticData::UndefinedDefinition { .. } => true,
+pub(crate) const VALIDATION_CODE: &str = "validation";
 
-        DiagnosticData::CapitalizedValue { .. } => false,
-        DiagnosticData::LimitExceeded { .. } => false,
-        DiagnosticData::MissingIdent => false,
-        DiagnosticData::ExecutableDefinition { .. } => false,
-        DiagnosticData::SingleRootField { .. } => false,
-        DiagnosticData::UnsupportedOperation { .. } => false,
-        DiagnosticData::UndefinedDirective { .. } => false,
-        DiagnosticData::UndefinedVariable { .. } => false,
-        DiagnosticData::UndefinedFragment { .. } => false,
-        DiagnosticData::UndefinedValue { .. } => false,
-        DiagnosticData::WrongTypeExtension { .. } => false,
-        DiagnosticData::UniqueField { .. } => false,
-        DiagnosticData::RequiredArgument { .. } => false,
-        DiagnosticData::ScalarSpecificationURL { .. } => false,
-        DiagnosticData::QueryRootOperationType => false,
-        DiagnosticData::BuiltInScalarDefinition => false,
-        DiagnosticData::VariableInputType { .. } => false,
-        DiagnosticData::UnusedVariable { .. } => false,
-        DiagnosticData::ObjectType { .. } => false,
-        DiagnosticData::UnsupportedDirectiveLocation { .. } => false,
-        DiagnosticData::UnsupportedValueType { .. } => false,
-        DiagnosticData::IntCoercionError { .. } => false,
-        DiagnosticData::UniqueDirective { .. } => false,
-        DiagnosticData::IntrospectionField { .. } => false,
-        DiagnosticData::DisallowedSubselection { .. } => false,
-        DiagnosticData::MissingSubselection { .. } => false,
-        DiagnosticData::ConflictingField { .. } => false,
-        DiagnosticData::InvalidFragment { .. } => false,
-        DiagnosticData::InvalidFragmentTarget { .. } => false,
-        DiagnosticData::InvalidFragmentSpread { .. } => false,
-        DiagnosticData::UnusedFragment { .. } => false,
-        DiagnosticData::DisallowedVariableUsage { .. } => false,
-        _ => false,
+/// Lint of the diagnostics added by newer versions of apollo-compiler.
+const OTHER_LINT: &str = "other";
+
+/// Declares the lints along with the diagnostics they are made of, so that every
+/// diagnostic that has a lint can be allowed or denied by its name.
+macro_rules! lints {
+    (
+        fatal: [$($fatal:ident),* $(,)?],
+        lints: [$($variant:ident => $name:literal),* $(,)?] $(,)?
+    ) => {
+        /// Every lint that can be allowed or denied.
+        pub(crate) const LINTS: &[&str] = &[$($name,)* OTHER_LINT];
+
+        /// Name of the lint that a non fatal diagnostic belongs to, which is used to allow it
+        /// or deny it. Fatal diagnostics have none, as they are always errors.
+        pub(crate) fn lint_name(diagnostic: &ApolloDiagnostic) -> Option<&'static str> {
+            match *diagnostic.data {
+                $(DiagnosticData::$fatal { .. } => None,)*
+                $(DiagnosticData::$variant { .. } => Some($name),)*
+                _ => Some(OTHER_LINT),
+            }
+        }
+    };
+}
+
+lints! {
+    fatal: [
+        SyntaxError,
+        UniqueDefinition,
+        UniqueArgument,
+        UniqueInputValue,
+        UniqueEnumValue,
+        RecursiveDirectiveDefinition,
+        RecursiveInterfaceDefinition,
+        RecursiveInputObjectDefinition,
+        RecursiveFragmentDefinition,
+        DuplicateImplementsInterface,
+        TransitiveImplementedInterfaces,
+        OutputType,
+        InputType,
+        MissingField,
+        UndefinedField,
+        UndefinedArgument,
+        UndefinedDefinition,
+    ],
+    lints: [
+        CapitalizedValue => "capitalized-value",
+        LimitExceeded => "limit-exceeded",
+        MissingIdent => "missing-ident",
+        ExecutableDefinition => "executable-definition",
+        SingleRootField => "single-root-field",
+        UnsupportedOperation => "unsupported-operation",
+        UndefinedDirective => "undefined-directive",
+        UndefinedVariable => "undefined-variable",
+        UndefinedFragment => "undefined-fragment",
+        UndefinedValue => "undefined-value",
+        WrongTypeExtension => "wrong-type-extension",
+        UniqueField => "unique-field",
+        RequiredArgument => "required-argument",
+        ScalarSpecificationURL => "scalar-specification-url",
+        QueryRootOperationType => "query-root-operation-type",
+        BuiltInScalarDefinition => "built-in-scalar-definition",
+        VariableInputType => "variable-input-type",
+        UnusedVariable => "unused-variable",
+        ObjectType => "object-type",
+        UnsupportedDirectiveLocation => "unsupported-directive-location",
+        UnsupportedValueType => "unsupported-value-type",
+        IntCoercionError => "int-coercion-error",
+        UniqueDirective => "unique-directive",
+        IntrospectionField => "introspection-field",
+        DisallowedSubselection => "disallowed-subselection",
+        MissingSubselection => "missing-subselection",
+        ConflictingField => "conflicting-field",
+        InvalidFragment => "invalid-fragment",
+        InvalidFragmentTarget => "invalid-fragment-target",
+        InvalidFragmentSpread => "invalid-fragment-spread",
+        UnusedFragment => "unused-fragment",
+        DisallowedVariableUsage => "disallowed-variable-usage",
+    ],
+}
+
+/// apollo-parser reports an object type without fields, like `type Viewer`, as a syntax
+/// error when another definition follows it, even though it is valid GraphQL.
+pub(crate) fn is_fieldless_type_error(diagnostic: &ApolloDiagnostic, schema: &str) -> bool {
+    let DiagnosticData::SyntaxError { message } = &*diagnostic.data else {
+        return false;
+    };
+    if message != "unexpected Name" {
+        return false;
     }
+    let Some(before) = schema.get(..diagnostic.location.offset()) else {
+        return false;
+    };
+    let last_line = before.trim_end().rsplit('\n').next().unwrap_or_default();
+    let words: Vec<&str> = last_line.split_whitespace().collect();
+    matches!(words.as_slice(), ["type", _])
 }
 
+/// Locates a diagnostic of the generated schema in the .graphqxl file it came from, it
+/// is reported with `code`, which is the name of its lint if it is not fatal.
 pub(crate) fn reverse_diagnostic_map(
     diagnostic: &ApolloDiagnostic,
-    source_map: &[SourceMapEntry],
-) -> Result<()> {
-    let source_offset = diagnostic.location.offset();
-    let source_length = diagnostic.location.node_len();
-    for entry in source_map.iter() {
-        let dst_start = source_offset;
-        let dst_end = source_offset + source_length;
-        let src_start = entry.start;
-        let src_end = entry.stop;
-        if (src_start <= dst_start && dst_end <= src_end) // if generated span is contained between source span limits
-            || (dst_start <= src_start && src_end <= dst_end)
-        // if source span is contained between generated span limits
-        {
-            let err = entry.span.make_error(&diagnostic.data.to_string());
-            return Err(anyhow!("{err}"));
+    source_map: &SourceMap,
+    code: &str,
+) -> DiagnosticError {
+    // apollo points the problems with the type of a field or an argument to all of it
+    let about_the_type = matches!(
+        &*diagnostic.data,
+        DiagnosticData::OutputType { .. } | DiagnosticData::InputType { .. }
+    );
+    let locate = |location: &DiagnosticLocation| {
+        let start = location.offset();
+        let end = start + location.node_len();
+        match about_the_type {
+            true => source_map.locate_value_type(start, end),
+            false => source_map.locate(start, end),
+        }
+    };
+    if let Some(span) = locate(&diagnostic.location) {
+        let mut err = span.make_error(&diagnostic.data.to_string());
+        // the labels in other files, like the built-in types, cannot be located
+        for label in diagnostic.labels.iter() {
+            if label.location.file_id() != diagnostic.location.file_id() {
+                continue;
+            }
+            match locate(&label.location) {
+                Some(label_span) if label_span.start != span.start => {
+                    err = err.with_label(label_span.make_label(&label.text))
+                }
+                _ => {}
+            }
+        }
+        if let Some(help) = &diagnostic.help {
+            err = err.with_note(help);
         }
+        return DiagnosticError::located(&err, code);
     }
 
-    Err(anyhow!("{diagnostic}"))
+    let unlocated = Diagnostic::new(code, &diagnostic.data.to_string());
+    DiagnosticError::new(unlocated, diagnostic.to_string())
 }
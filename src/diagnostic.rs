@@ -0,0 +1,298 @@
+use graphqxl_parser::{ErrorList, LocatedError};
+use serde::Serialize;
+use std::env;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+}
+
+/// A place in a file, lines and columns start at 1 and offsets are in bytes.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Position {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) offset: usize,
+}
+
+/// A problem found while compiling, in a form that tools can read.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    pub(crate) file: Option<String>,
+    pub(crate) start: Option<Position>,
+    pub(crate) end: Option<Position>,
+    pub(crate) severity: Severity,
+    pub(crate) code: String,
+    pub(crate) message: String,
+}
+
+impl Diagnostic {
+    /// Builds a diagnostic without a location.
+    pub(crate) fn new(code: &str, message: &str) -> Self {
+        Self {
+            file: None,
+            start: None,
+            end: None,
+            severity: Severity::Error,
+            code: code.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Builds a diagnostic out of an error raised at the stage identified by `code`, syntax
+    /// errors get their own code.
+    pub(crate) fn from_error(err: &(dyn Error + 'static), code: &str) -> Self {
+        let err = match err.downcast_ref::<LocatedError>() {
+            Some(err) => err,
+            None => return Self::new(code, &err.to_string()),
+        };
+        let (start_offset, end_offset) = err.offsets();
+        let ((start_line, start_column), (end_line, end_column)) = err.line_cols();
+        let code = match err.is_syntax_error() {
+            true => "syntax",
+            false => code,
+        };
+        Self {
+            file: Some(err.file.clone()),
+            start: Some(Position {
+                line: start_line,
+                column: start_column,
+                offset: start_offset,
+            }),
+            end: Some(Position {
+                line: end_line,
+                column: end_column,
+                offset: end_offset,
+            }),
+            ..Self::new(code, &err.message)
+        }
+    }
+}
+
+/// How a diagnostic is shown to humans.
+#[derive(Debug)]
+enum Rendering {
+    /// Along with the code it points to
+    Located(LocatedError),
+    /// Only its message
+    Message,
+    /// Already rendered by whoever found it
+    Raw(String),
+}
+
+/// A diagnostic along with the way it is shown to humans, and the message of the stage
+/// that raised it, if any.
+#[derive(Debug)]
+struct Reported {
+    diagnostic: Diagnostic,
+    context: Option<String>,
+    lint: Option<String>,
+    rendering: Rendering,
+}
+
+impl Reported {
+    fn new(diagnostic: Diagnostic, rendering: Rendering) -> Self {
+        Self {
+            diagnostic,
+            context: None,
+            lint: None,
+            rendering,
+        }
+    }
+
+    fn from_error(err: &(dyn Error + 'static), code: &str) -> Self {
+        let rendering = match err.downcast_ref::<LocatedError>() {
+            Some(err) => Rendering::Located(err.clone()),
+            None => Rendering::Message,
+        };
+        Self::new(Diagnostic::from_error(err, code), rendering)
+    }
+
+    fn render(&self, color: bool) -> String {
+        let mut level = match self.diagnostic.severity {
+            Severity::Error => "error".to_string(),
+            Severity::Warning => "warning".to_string(),
+        };
+        if let Some(lint) = &self.lint {
+            level += &format!("[{lint}]");
+        }
+        match &self.rendering {
+            Rendering::Located(err) => relative_paths(err).render(&level, color),
+            Rendering::Message => {
+                let message = format!("{level}: {}", self.diagnostic.message);
+                LocatedError::paint_header(&message, color)
+            }
+            Rendering::Raw(rendered) => rendered.clone(),
+        }
+    }
+}
+
+/// Shows the paths of the files under the current directory relative to it, which is
+/// where the compiler is usually run from.
+fn relative_paths(err: &LocatedError) -> LocatedError {
+    let mut err = err.clone();
+    if let Ok(dir) = env::current_dir() {
+        let relative = |file: &mut String| {
+            if let Ok(path) = Path::new(file.as_str()).strip_prefix(&dir) {
+                *file = path.to_string_lossy().to_string();
+            }
+        };
+        relative(&mut err.file);
+        err.labels
+            .iter_mut()
+            .for_each(|label| relative(&mut label.file));
+    }
+    err
+}
+
+/// Errors that show as usual, but also carry the diagnostics describing them. It is
+/// also used for the warnings, which are reported in the same way.
+#[derive(Debug, Default)]
+pub(crate) struct DiagnosticError {
+    reported: Vec<Reported>,
+    omitted: usize,
+}
+
+impl DiagnosticError {
+    /// Builds an error that was already rendered.
+    pub(crate) fn new(diagnostic: Diagnostic, rendered: String) -> Self {
+        Self {
+            reported: vec![Reported::new(diagnostic, Rendering::Raw(rendered))],
+            omitted: 0,
+        }
+    }
+
+    /// Builds an error that points to a place of a file, raised at the stage identified
+    /// by `code`.
+    pub(crate) fn located(err: &LocatedError, code: &str) -> Self {
+        Self {
+            reported: vec![Reported::from_error(err, code)],
+            omitted: 0,
+        }
+    }
+
+    /// Builds one diagnostic for each of the errors raised at the stage identified by
+    /// `code`, they are shown after `context`.
+    pub(crate) fn from_errors(err: Box<dyn Error>, context: &str, code: &str) -> Self {
+        let reported = ErrorList::flatten(err)
+            .iter()
+            .map(|err| Reported {
+                context: Some(context.to_string()),
+                ..Reported::from_error(err.as_ref(), code)
+            })
+            .collect();
+        Self {
+            reported,
+            omitted: 0,
+        }
+    }
+
+    /// Puts together the errors found in different places, errors that were not raised
+    /// by the compiler itself are kept without a location.
+    pub(crate) fn merge(errors: Vec<anyhow::Error>) -> Self {
+        let mut merged = Self::default();
+        for err in errors {
+            let err = match err.downcast::<DiagnosticError>() {
+                Ok(err) => err,
+                Err(err) => Self {
+                    reported: vec![Reported::new(
+                        Diagnostic::new("error", &err.to_string()),
+                        Rendering::Message,
+                    )],
+                    omitted: 0,
+                },
+            };
+            merged.reported.extend(err.reported);
+            merged.omitted += err.omitted;
+        }
+        merged
+    }
+
+    /// Shows the name of the lint the errors belong to along with them.
+    pub(crate) fn with_lint(mut self, lint: &str) -> Self {
+        for reported in self.reported.iter_mut() {
+            reported.lint = Some(lint.to_string());
+        }
+        self
+    }
+
+    /// Turns the errors into warnings.
+    pub(crate) fn into_warnings(mut self) -> Self {
+        for reported in self.reported.iter_mut() {
+            reported.diagnostic.severity = Severity::Warning;
+        }
+        self
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.reported.is_empty() && self.omitted == 0
+    }
+
+    pub(crate) fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.reported
+            .iter()
+            .map(|reported| reported.diagnostic.clone())
+            .collect()
+    }
+
+    /// Keeps only the first `max` errors, 0 means that there is no limit. The ones that
+    /// are left out are only counted.
+    pub(crate) fn truncate(&mut self, max: usize) {
+        if max > 0 && self.reported.len() > max {
+            self.omitted += self.reported.len() - max;
+            self.reported.truncate(max);
+        }
+    }
+
+    /// Shows every error along with the code it points to, `color` adds ANSI colors
+    /// for terminals.
+    pub(crate) fn render(&self, color: bool) -> String {
+        let mut out = String::new();
+        let mut context = None;
+        for (i, reported) in self.reported.iter().enumerate() {
+            if i > 0 {
+                out += "\n\n";
+            }
+            // consecutive errors raised at the same stage share their message
+            if let Some(msg) = &reported.context {
+                if context != Some(msg) {
+                    out += &format!("{msg}:\n\n");
+                }
+            }
+            context = reported.context.as_ref();
+            out += &reported.render(color);
+        }
+        if self.omitted > 0 {
+            out += &format!("\n\n... and {} more error(s)", self.omitted);
+        }
+        out
+    }
+}
+
+impl Display for DiagnosticError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(false))
+    }
+}
+
+impl Error for DiagnosticError {}
+
+/// Serializes the diagnostics of an error as a JSON array, errors that were not raised
+/// by the compiler itself are reported without a location.
+pub(crate) fn diagnostics_json(err: &anyhow::Error) -> String {
+    let diagnostics = match err.downcast_ref::<DiagnosticError>() {
+        Some(err) => err.diagnostics(),
+        None => vec![Diagnostic::new("error", &err.to_string())],
+    };
+    serde_json::to_string(&diagnostics).unwrap()
+}
+
+/// Serializes the diagnostics of some warnings as a JSON array.
+pub(crate) fn warnings_json(warnings: &DiagnosticError) -> String {
+    serde_json::to_string(&warnings.diagnostics()).unwrap()
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+
+/// Path that stands for stdin when used as an input, and for stdout as an output.
+pub(crate) const STDIO: &str = "-";
+
+/// A file to compile, and the path where the compiled schema is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entrypoint {
+    pub(crate) input: String,
+    pub(crate) output: String,
+}
+
+impl Entrypoint {
+    /// Builds an entrypoint that writes next to its input, replacing the `.graphqxl`
+    /// extension by `.graphql`, unless another output is given. Stdin is written to
+    /// stdout by default.
+    pub(crate) fn new(input: &str, output: Option<&str>) -> Self {
+        let input_path = Path::new(input);
+        let output = if let Some(output) = output {
+            output.to_string()
+        } else if input == STDIO {
+            STDIO.to_string()
+        } else if input_path.extension().is_some_and(|ext| ext == "graphqxl") {
+            input_path
+                .with_extension("graphql")
+                .to_string_lossy()
+                .to_string()
+        } else {
+            input.to_string() + ".graphql"
+        };
+        Self {
+            input: input.to_string(),
+            output,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_output() {
+        assert_eq!(
+            Entrypoint::new("schema.graphqxl", None).output,
+            "schema.graphql"
+        );
+        assert_eq!(Entrypoint::new("schema", None).output, "schema.graphql");
+        assert_eq!(
+            Entrypoint::new("schema.graphqxl", Some("out.graphql")).output,
+            "out.graphql"
+        );
+        assert_eq!(
+            Entrypoint::new("dir.graphqxl/schema", None).output,
+            "dir.graphqxl/schema.graphql"
+        );
+        assert_eq!(Entrypoint::new("-", None).output, "-");
+    }
+}
@@ -1,94 +1,586 @@
 mod apollo_diagnostic_source;
+mod diagnostic;
+mod entrypoint;
 mod ok_or_anyhow_err;
+mod stdin_file_system;
+#[cfg(test)]
+mod temp_dir;
+mod watch;
 
-use crate::apollo_diagnostic_source::{is_fatal_diagnostic, reverse_diagnostic_map};
+use crate::apollo_diagnostic_source::{
+    is_fieldless_type_error, lint_name, reverse_diagnostic_map, LINTS, VALIDATION_CODE,
+};
+use crate::diagnostic::{diagnostics_json, warnings_json, DiagnosticError};
+use crate::entrypoint::{Entrypoint, STDIO};
 use crate::ok_or_anyhow_err::ok_or_anyhow_err;
-use anyhow::Result;
+use crate::stdin_file_system::StdinFileSystem;
+use crate::watch::Watcher;
+use anyhow::{anyhow, Result};
 use apollo_compiler::ApolloCompiler;
-use clap::Parser;
-use graphqxl_parser::parse_spec;
-use graphqxl_synthesizer::{synth_spec, SynthConfig};
-use graphqxl_transpiler::{transpile_spec, TranspileSpecOptions};
-use std::fs;
+use clap::{Parser, ValueEnum};
+use graphqxl_config::{Config, EntrypointConfig, FormatConfig, LintLevel, LintsConfig};
+use graphqxl_parser::{
+    parse_specs_vfs_with_cache, NativeFileSystem, ParseCache, Spec, VirtualFileSystem,
+};
+use graphqxl_synthesizer::synth_spec;
+use graphqxl_transpiler::{transpile_spec_with_cache, TranspileCache};
+use similar::TextDiff;
+use std::collections::HashMap;
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs, io, process, thread};
+
+/// How often the files are checked for changes in watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_millis(300);
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ErrorFormat {
+    /// Errors are shown along with the code they point to
+    Human,
+    /// Errors are written to stderr as a JSON array of diagnostics
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorChoice {
+    /// Colors are used if stderr is a terminal and NO_COLOR is not set
+    Auto,
+    Always,
+    Never,
+}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(help = "Path to the .graphqxl file")]
-    input: String,
+    #[arg(
+        help = "Paths to the .graphqxl files, a .graphql file is generated for each one, - reads from stdin"
+    )]
+    input: Vec<String>,
 
-    #[arg(short, long, help = "Output path for the generated .graphql file")]
+    #[arg(
+        short,
+        long,
+        help = "Output path for the generated .graphql file, only if there is a single input, - writes to stdout"
+    )]
     output: Option<String>,
 
     #[arg(
         long,
-        default_value_t = 2,
-        help = "Number of spaces used for the generated file's indentation"
+        help = "Directory where the imports of the input read from stdin are resolved [default: current directory]"
     )]
-    indent_spaces: usize,
+    base_dir: Option<String>,
 
     #[arg(
         long,
-        default_value_t = String::from("_"),
-        help = "String that needs to be prefixed to a type or an input in order to consider it private"
+        help = "Path to the config file, by default a graphqxl.toml is looked up in the directory of the first input and in its parents"
     )]
-    private_prefix: String,
+    config: Option<String>,
+
+    #[arg(
+        long,
+        help = "Number of spaces used for the generated file's indentation [default: 2]"
+    )]
+    indent_spaces: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Maximum number of arguments that a field can have for them to be written in one line [default: 2]"
+    )]
+    max_one_line_args: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Maximum number of members that a union or a directive's locations can have for them to be written in one line [default: 2]"
+    )]
+    max_one_line_ors: Option<usize>,
+
+    #[arg(long, help = "Allow values to be written in multiple lines")]
+    allow_multiline_values: bool,
+
+    #[arg(
+        long,
+        help = "String that needs to be prefixed to a type or an input in order to consider it private [default: _]"
+    )]
+    private_prefix: Option<String>,
+
+    #[arg(
+        long,
+        help = "Pattern for naming the types generated by inline generic instantiations, {name} is the generic type's name and {args} the names of its arguments [default: {args}{name}]"
+    )]
+    inline_generic_name: Option<String>,
+
+    #[arg(
+        long = "import-root",
+        help = "Directory where package-style imports are looked up, can be passed multiple times"
+    )]
+    import_roots: Vec<String>,
+
+    #[arg(
+        long,
+        help = "String placed between the namespace of an import and the names of the definitions it brings [default: \"\"]"
+    )]
+    namespace_separator: Option<String>,
+
+    #[arg(
+        long,
+        help = "Maximum number of errors reported at once, 0 reports all of them [default: 20]"
+    )]
+    max_errors: Option<usize>,
+
+    #[arg(long, help = "Fail when there are warnings")]
+    deny_warnings: bool,
+
+    #[arg(
+        long = "allow",
+        value_name = "LINT",
+        help = "Do not report the warnings of a lint, can be passed multiple times"
+    )]
+    allow: Vec<String>,
+
+    #[arg(
+        long = "warn",
+        value_name = "LINT",
+        help = "Report the warnings of a lint that is allowed by default, can be passed multiple times"
+    )]
+    warn: Vec<String>,
+
+    #[arg(
+        long = "deny",
+        value_name = "LINT",
+        help = "Report the warnings of a lint as errors, can be passed multiple times"
+    )]
+    deny: Vec<String>,
+
+    #[arg(
+        short,
+        long,
+        help = "Keep running and compile again every time one of the imported files changes"
+    )]
+    watch: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "watch",
+        help = "Do not write anything, fail with a diff if the output files are not up to date"
+    )]
+    check: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ErrorFormat::Human,
+        help = "How errors are reported"
+    )]
+    error_format: ErrorFormat,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ColorChoice::Auto,
+        help = "Whether errors are shown with colors"
+    )]
+    color: ColorChoice,
 }
 
-fn graphqxl_to_graphql(args: &Args) -> Result<(String, String)> {
-    let out_path = if let Some(out_path) = &args.output {
-        out_path.to_string()
-    } else if args.input.ends_with("graphqxl") {
-        args.input[..args.input.len() - 2].to_string() + "l"
+/// Reads the config file, if there is one, and overrides its settings with the
+/// ones passed in the command line.
+fn config(args: &Args, vfs: &dyn VirtualFileSystem) -> Result<Config> {
+    let mut config = if let Some(path) = &args.config {
+        let config_result = Config::read(Path::new(path), vfs);
+        ok_or_anyhow_err(config_result, "Could not read config file", "config")?
     } else {
-        args.input.to_string() + ".graphql"
+        let dir = match args.input.first() {
+            Some(input) => vfs
+                .canonicalize(Path::new(input))
+                .ok()
+                .and_then(|path| path.parent().map(Path::to_path_buf)),
+            None => env::current_dir().ok(),
+        };
+        let config_result = match dir {
+            Some(dir) => Config::find(&dir, vfs),
+            None => Ok(None),
+        };
+        ok_or_anyhow_err(config_result, "Could not read config file", "config")?.unwrap_or_default()
     };
+    config.override_with(Config {
+        entrypoints: args
+            .input
+            .iter()
+            .map(|input| EntrypointConfig {
+                input: PathBuf::from(input),
+                output: args.output.as_ref().map(PathBuf::from),
+            })
+            .collect(),
+        import_roots: args.import_roots.iter().map(PathBuf::from).collect(),
+        namespace_separator: args.namespace_separator.clone(),
+        private_prefix: args.private_prefix.clone(),
+        inline_generic_name: args.inline_generic_name.clone(),
+        max_errors: args.max_errors,
+        format: FormatConfig {
+            indent_spaces: args.indent_spaces,
+            max_one_line_args: args.max_one_line_args,
+            max_one_line_ors: args.max_one_line_ors,
+            allow_multiline_values: args.allow_multiline_values.then_some(true),
+        },
+        lints: LintsConfig {
+            allow: args.allow.clone(),
+            warn: args.warn.clone(),
+            deny: args.deny.clone(),
+            deny_warnings: args.deny_warnings.then_some(true),
+        },
+    });
+    ok_or_anyhow_err(check_lints(&config), "Invalid lints", "config")?;
+    Ok(config)
+}
 
-    let spec_result = parse_spec(&args.input);
-    let spec = ok_or_anyhow_err(spec_result, "Could not parse GraphQXL spec")?;
+/// Fails if a lint that does not exist is allowed or denied, as it would have no effect.
+fn check_lints(config: &Config) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let lints = &config.lints;
+    for lint in lints.allow.iter().chain(&lints.warn).chain(&lints.deny) {
+        if !LINTS.contains(&lint.as_str()) {
+            return Err(format!(
+                "unknown lint \"{lint}\", the existing ones are: {}",
+                LINTS.join(", ")
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
 
-    let transpile_result = transpile_spec(
-        &spec,
-        &TranspileSpecOptions {
-            private_prefix: args.private_prefix.clone(),
-        },
-    );
-    let transpiled = ok_or_anyhow_err(transpile_result, "Could not transpile graphqxl spec")?;
+fn entrypoints(args: &Args, config: &Config) -> Result<Vec<Entrypoint>> {
+    if args.output.is_some() && args.input.len() != 1 {
+        return Err(anyhow!(
+            "--output can only be used with a single input, use a config file to set the output of each entrypoint"
+        ));
+    }
+    let entrypoints: Vec<Entrypoint> = config
+        .entrypoints
+        .iter()
+        .map(|entrypoint| {
+            Entrypoint::new(
+                &entrypoint.input.to_string_lossy(),
+                entrypoint
+                    .output
+                    .as_ref()
+                    .map(|output| output.to_string_lossy())
+                    .as_deref(),
+            )
+        })
+        .collect();
+    if entrypoints.is_empty() {
+        return Err(anyhow!(
+            "No input was given, pass the .graphqxl files to compile or list them in a graphqxl.toml file"
+        ));
+    }
+    Ok(entrypoints)
+}
 
-    let (result, source_map) = synth_spec(
-        transpiled,
-        SynthConfig {
-            indent_spaces: args.indent_spaces,
-            private_prefix: args.private_prefix.clone(),
-            ..Default::default()
-        },
+/// The generated schemas along with their output paths, and the warnings found in them.
+#[derive(Debug)]
+struct Compiled {
+    results: Vec<(String, String)>,
+    warnings: DiagnosticError,
+}
+
+/// What is kept from one compilation to the next one in watch mode, so that only the
+/// files that changed are parsed again, and only the definitions affected by them are
+/// transpiled again.
+#[derive(Default)]
+struct Caches {
+    parse: ParseCache,
+    /// The one of each entrypoint, by its output path.
+    transpile: HashMap<String, TranspileCache>,
+}
+
+/// Compiles every entrypoint.
+fn graphqxl_to_graphql(args: &Args) -> Result<Compiled> {
+    graphqxl_to_graphql_vfs(args, &NativeFileSystem, &mut Caches::default())
+}
+
+fn graphqxl_to_graphql_vfs(
+    args: &Args,
+    vfs: &dyn VirtualFileSystem,
+    caches: &mut Caches,
+) -> Result<Compiled> {
+    let config = config(args, vfs)?;
+    compile(args, &config, vfs, caches).map_err(|err| match err.downcast::<DiagnosticError>() {
+        Ok(mut err) => {
+            err.truncate(config.max_errors());
+            err.into()
+        }
+        Err(err) => err,
+    })
+}
+
+fn compile(
+    args: &Args,
+    config: &Config,
+    vfs: &dyn VirtualFileSystem,
+    caches: &mut Caches,
+) -> Result<Compiled> {
+    let entrypoints = entrypoints(args, config)?;
+    let inputs: Vec<&str> = entrypoints.iter().map(|e| e.input.as_str()).collect();
+    let specs_result = parse_specs_vfs_with_cache(
+        &inputs,
+        vfs,
+        &config.parse_spec_options(),
+        &mut caches.parse,
     );
+    let specs = ok_or_anyhow_err(specs_result, "Could not parse GraphQXL spec", "parse")?;
+
+    // an entrypoint that fails does not hide the errors of the next ones
+    let mut results = vec![];
+    let mut errors = vec![];
+    let mut warnings = vec![];
+    caches
+        .transpile
+        .retain(|output, _| entrypoints.iter().any(|e| &e.output == output));
+    for (entrypoint, spec) in entrypoints.into_iter().zip(specs) {
+        let cache = caches
+            .transpile
+            .entry(entrypoint.output.clone())
+            .or_default();
+        match spec_to_graphql(&spec, &entrypoint.output, config, cache) {
+            Ok((result, spec_warnings)) => {
+                results.push((result, entrypoint.output));
+                warnings.push(spec_warnings.into());
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+    if !errors.is_empty() {
+        // the warnings are not lost when something else fails
+        errors.extend(warnings);
+        return Err(DiagnosticError::merge(errors).into());
+    }
+    Ok(Compiled {
+        results,
+        warnings: DiagnosticError::merge(warnings),
+    })
+}
+
+/// Generates the schema of a spec, along with the warnings found in it.
+fn spec_to_graphql(
+    spec: &Spec,
+    out_path: &str,
+    config: &Config,
+    cache: &mut TranspileCache,
+) -> Result<(String, DiagnosticError)> {
+    let transpile_result = transpile_spec_with_cache(spec, &config.transpile_spec_options(), cache);
+    let transpiled = ok_or_anyhow_err(
+        transpile_result,
+        "Could not transpile graphqxl spec",
+        "transpile",
+    )?;
+
+    let (result, source_map) = synth_spec(transpiled, config.synth_config());
     let mut ctx = ApolloCompiler::new();
-    ctx.add_type_system(&result, &out_path);
-    let diagnostics = ctx.validate();
-    for diagnostic in diagnostics {
-        if is_fatal_diagnostic(&diagnostic) {
-            reverse_diagnostic_map(&diagnostic, &source_map)?;
+    ctx.add_type_system(&result, out_path);
+    let mut errors = vec![];
+    let mut warnings = vec![];
+    for diagnostic in ctx.validate().iter() {
+        if is_fieldless_type_error(diagnostic, &result) {
+            continue;
         }
+        let Some(lint) = lint_name(diagnostic) else {
+            errors.push(reverse_diagnostic_map(diagnostic, &source_map, VALIDATION_CODE).into());
+            continue;
+        };
+        // advices, such as custom scalars not being specified by a URL, are opt-in
+        let default = match diagnostic.data.is_advice() {
+            true => LintLevel::Allow,
+            false => LintLevel::Warn,
+        };
+        let located = reverse_diagnostic_map(diagnostic, &source_map, lint);
+        match config.lint_level(lint, default) {
+            LintLevel::Allow => {}
+            LintLevel::Warn => warnings.push(located.with_lint(lint).into_warnings().into()),
+            LintLevel::Deny => errors.push(located.with_lint(lint).into()),
+        }
+    }
+    if !errors.is_empty() {
+        errors.extend(warnings);
+        return Err(DiagnosticError::merge(errors).into());
     }
-    Ok((result, out_path))
+    Ok((result, DiagnosticError::merge(warnings)))
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let (result, out_path) = graphqxl_to_graphql(&args)?;
-    fs::write(out_path, result)?;
+fn reads_stdin(args: &Args) -> bool {
+    args.input.iter().any(|input| input == STDIO)
+}
+
+/// Reads stdin, so that it can be compiled as if it was a file of the base directory.
+fn stdin_file_system(args: &Args) -> Result<StdinFileSystem<'static>> {
+    let mut content = String::new();
+    io::stdin().read_to_string(&mut content)?;
+    let base_dir = args.base_dir.as_deref().unwrap_or(".");
+    let vfs_result = StdinFileSystem::new(content, Path::new(base_dir), &NativeFileSystem);
+    ok_or_anyhow_err(vfs_result, "Could not read the base directory", "io")
+}
+
+fn write_results(results: Vec<(String, String)>) -> Result<()> {
+    for (result, out_path) in results {
+        if out_path == STDIO {
+            io::stdout().write_all(result.as_bytes())?;
+        } else {
+            fs::write(out_path, result)?;
+        }
+    }
     Ok(())
 }
 
+/// Compares the results with the output files already on disk, failing with a unified
+/// diff of each one that is not up to date.
+fn check_results(results: Vec<(String, String)>) -> Result<()> {
+    let mut diffs = String::new();
+    let mut outdated = 0;
+    for (result, out_path) in results {
+        if out_path == STDIO {
+            return Err(anyhow!("The output written to stdout cannot be checked"));
+        }
+        let current = match fs::read_to_string(&out_path) {
+            Ok(current) => current,
+            // a missing output is reported as a diff against an empty file
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(anyhow!("Could not read {out_path}: {err}")),
+        };
+        if current == result {
+            continue;
+        }
+        outdated += 1;
+        let diff = TextDiff::from_lines(&current, &result);
+        diffs += &diff
+            .unified_diff()
+            .header(&out_path, &format!("{out_path} (generated)"))
+            .to_string();
+    }
+    if outdated > 0 {
+        return Err(anyhow!(
+            "{outdated} output file(s) are not up to date, run graphqxl without --check to update them:\n\n{diffs}"
+        ));
+    }
+    Ok(())
+}
+
+/// Compiles the entrypoints every time a file of their import graph changes, errors
+/// are printed without stopping. Only the files that changed are parsed again.
+fn watch(args: &Args) -> Result<()> {
+    if reads_stdin(args) {
+        return Err(anyhow!("The input read from stdin cannot be watched"));
+    }
+    let mut watcher = Watcher::default();
+    let mut caches = Caches::default();
+    loop {
+        let result = watcher.poll(|vfs| {
+            let compiled = graphqxl_to_graphql_vfs(args, vfs, &mut caches)?;
+            report_warnings(args, &compiled.warnings);
+            write_results(compiled.results)
+        });
+        let human = args.error_format == ErrorFormat::Human;
+        match result {
+            Some(Ok(())) if human => eprintln!("Compiled successfully, watching for changes..."),
+            Some(Err(err)) => {
+                report_error(args, &err);
+                if human {
+                    eprintln!("\nWatching for changes...");
+                }
+            }
+            _ => {}
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+fn use_color(args: &Args) -> bool {
+    match args.color {
+        ColorChoice::Auto => io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    }
+}
+
+fn report_error(args: &Args, err: &anyhow::Error) {
+    match (args.error_format, err.downcast_ref::<DiagnosticError>()) {
+        (ErrorFormat::Human, Some(err)) => eprintln!("{}", err.render(use_color(args))),
+        (ErrorFormat::Human, None) => eprintln!("Error: {err:?}"),
+        (ErrorFormat::Json, _) => eprintln!("{}", diagnostics_json(err)),
+    }
+}
+
+fn report_warnings(args: &Args, warnings: &DiagnosticError) {
+    if warnings.is_empty() {
+        return;
+    }
+    match args.error_format {
+        ErrorFormat::Human => eprintln!("{}\n", warnings.render(use_color(args))),
+        ErrorFormat::Json => eprintln!("{}", warnings_json(warnings)),
+    }
+}
+
+fn run(args: &Args) -> Result<()> {
+    if args.watch {
+        return watch(args);
+    }
+    let compiled = if reads_stdin(args) {
+        graphqxl_to_graphql_vfs(args, &stdin_file_system(args)?, &mut Caches::default())?
+    } else {
+        graphqxl_to_graphql(args)?
+    };
+    report_warnings(args, &compiled.warnings);
+    if args.check {
+        return check_results(compiled.results);
+    }
+    write_results(compiled.results)
+}
+
+fn main() {
+    let args = Args::parse();
+    if let Err(err) = run(&args) {
+        report_error(&args, &err);
+        process::exit(1);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use regex::Regex;
+    use crate::diagnostic::{Diagnostic, Position, Severity};
+    use crate::temp_dir::TempDir;
     use std::path::Path;
 
     const ONLY: &str = "";
 
+    fn args(input: Vec<PathBuf>, config: Option<PathBuf>) -> Args {
+        Args {
+            input: input
+                .iter()
+                .map(|path| path.to_str().unwrap().to_string())
+                .collect(),
+            output: None,
+            base_dir: None,
+            config: config.map(|path| path.to_str().unwrap().to_string()),
+            indent_spaces: None,
+            max_one_line_args: None,
+            max_one_line_ors: None,
+            allow_multiline_values: false,
+            private_prefix: None,
+            inline_generic_name: None,
+            import_roots: vec![],
+            namespace_separator: None,
+            max_errors: None,
+            deny_warnings: false,
+            allow: vec![],
+            warn: vec![],
+            deny: vec![],
+            watch: false,
+            check: false,
+            error_format: ErrorFormat::Human,
+            color: ColorChoice::Never,
+        }
+    }
+
     #[test]
     fn test_graphqxl_to_graphql() {
         let test_dir = Path::new("src").join("test");
@@ -102,18 +594,11 @@ mod tests {
             {
                 continue;
             }
-            let result = graphqxl_to_graphql(&Args {
-                input: test_dir.join(path).to_str().unwrap().to_string(),
-                output: None,
-                indent_spaces: 2,
-                private_prefix: "_".to_string(),
-            });
-            let result = if let Ok((result, _)) = result {
-                result
+            let result = graphqxl_to_graphql(&args(vec![test_dir.join(path)], None));
+            let result = if let Ok(mut compiled) = result {
+                compiled.results.remove(0).0
             } else {
-                let err = format!("{}", result.unwrap_err());
-                let re = Regex::new(r"(/.+)+\.graphqxl").unwrap();
-                re.replace_all(&err, "").to_string()
+                format!("{}", result.unwrap_err())
             };
             let out_path = test_dir.join(path.to_string() + ".result");
             if out_path.exists() {
@@ -124,4 +609,230 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_compiles_the_same_with_the_caches() {
+        let test_dir = Path::new("src").join("test");
+        for dir_entry in fs::read_dir(&test_dir).unwrap() {
+            let file_name = dir_entry.unwrap().file_name();
+            let path = file_name.to_str().unwrap();
+            if path.starts_with('_') || path.ends_with("result") {
+                continue;
+            }
+            let args = args(vec![test_dir.join(path)], None);
+            let mut caches = Caches::default();
+            let mut compile =
+                || match graphqxl_to_graphql_vfs(&args, &NativeFileSystem, &mut caches) {
+                    Ok(mut compiled) => compiled.results.remove(0).0,
+                    Err(err) => err.to_string(),
+                };
+            assert_eq!(compile(), compile(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_compiles_several_entrypoints() {
+        let test_dir = Path::new("src").join("test").join("_entrypoints");
+        let results = graphqxl_to_graphql(&args(
+            vec![
+                test_dir.join("public.graphqxl"),
+                test_dir.join("internal.graphqxl"),
+            ],
+            None,
+        ))
+        .unwrap()
+        .results;
+        assert_eq!(results[0].1, "src/test/_entrypoints/public.graphql");
+        assert!(results[0].0.contains("user: User"));
+        assert_eq!(results[1].1, "src/test/_entrypoints/internal.graphql");
+        assert!(results[1].0.contains("users: [User!]!"));
+    }
+
+    #[test]
+    fn test_reads_entrypoints_from_config() {
+        let test_dir = Path::new("src").join("test").join("_entrypoints");
+        let results = graphqxl_to_graphql(&args(vec![], Some(test_dir.join("graphqxl.toml"))))
+            .unwrap()
+            .results;
+        let out_paths: Vec<&str> = results.iter().map(|(_, path)| path.as_str()).collect();
+        assert_eq!(
+            out_paths,
+            vec![
+                "src/test/_entrypoints/public.graphql",
+                "src/test/_entrypoints/dist/internal.graphql",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_compares_with_the_output_files() {
+        let dir = TempDir::new("check");
+        let out_path = dir.join("schema.graphql").to_str().unwrap().to_string();
+        let result = "type Query {\n  a: String\n}\n".to_string();
+
+        let err = check_results(vec![(result.clone(), out_path.clone())]).unwrap_err();
+        assert!(err.to_string().contains("+  a: String"));
+
+        fs::write(&out_path, "type Query {\n  b: String\n}\n").unwrap();
+        let err = check_results(vec![(result.clone(), out_path.clone())]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("1 output file(s) are not up to date"));
+        assert!(err.to_string().contains("-  b: String\n+  a: String"));
+        assert_eq!(
+            fs::read_to_string(&out_path).unwrap(),
+            "type Query {\n  b: String\n}\n"
+        );
+
+        fs::write(&out_path, &result).unwrap();
+        assert!(check_results(vec![(result.clone(), out_path)]).is_ok());
+
+        // outputs that exist but cannot be read are not taken as empty
+        let dir_path = dir.to_str().unwrap().to_string();
+        let err = check_results(vec![(result, dir_path)]).unwrap_err();
+        assert!(err.to_string().starts_with("Could not read"));
+    }
+
+    #[test]
+    fn test_reads_from_stdin() {
+        let vfs = StdinFileSystem::new(
+            "import \"_common\"\n\ntype Query { address: Address }\n".to_string(),
+            &Path::new("src").join("test"),
+            &NativeFileSystem,
+        )
+        .unwrap();
+        let results = graphqxl_to_graphql_vfs(
+            &args(vec![PathBuf::from(STDIO)], None),
+            &vfs,
+            &mut Caches::default(),
+        )
+        .unwrap()
+        .results;
+        assert_eq!(results[0].1, STDIO);
+        assert!(results[0].0.contains("type Address {"));
+        assert!(results[0].0.contains("address: Address"));
+    }
+
+    fn diagnostics(err: anyhow::Error) -> Vec<Diagnostic> {
+        err.downcast::<DiagnosticError>().unwrap().diagnostics()
+    }
+
+    fn diagnostic(err: anyhow::Error) -> Diagnostic {
+        diagnostics(err).remove(0)
+    }
+
+    #[test]
+    fn test_reports_transpile_errors_as_diagnostics() {
+        let path = Path::new("src")
+            .join("test")
+            .join("bad-pick-field.graphqxl");
+        let err = graphqxl_to_graphql(&args(vec![path], None)).unwrap_err();
+        let diagnostic = diagnostic(err);
+        assert!(diagnostic
+            .file
+            .unwrap()
+            .ends_with("src/test/bad-pick-field.graphqxl"));
+        assert_eq!(diagnostic.code, "transpile");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        let start = diagnostic.start.unwrap();
+        let end = diagnostic.end.unwrap();
+        assert!(start.line > 1);
+        assert!(start.offset < end.offset);
+        assert!(!diagnostic.message.contains("bad-pick-field"));
+    }
+
+    #[test]
+    fn test_reports_syntax_errors_as_diagnostics() {
+        let vfs = StdinFileSystem::new(
+            "type Query {\n  a: String\n".to_string(),
+            &Path::new("src").join("test"),
+            &NativeFileSystem,
+        )
+        .unwrap();
+        let err = graphqxl_to_graphql_vfs(
+            &args(vec![PathBuf::from(STDIO)], None),
+            &vfs,
+            &mut Caches::default(),
+        )
+        .unwrap_err();
+        let json = diagnostics_json(&err);
+        let diagnostic = diagnostic(err);
+        assert!(diagnostic.file.unwrap().ends_with("src/test/<stdin>"));
+        assert_eq!(diagnostic.code, "syntax");
+        assert_eq!(
+            diagnostic.start,
+            Some(Position {
+                line: 3,
+                column: 1,
+                offset: 25
+            })
+        );
+        assert!(json.starts_with("[{\"file\":"));
+        assert!(json.contains("\"severity\":\"error\",\"code\":\"syntax\""));
+    }
+
+    #[test]
+    fn test_reports_errors_up_to_the_maximum() {
+        let path = Path::new("src")
+            .join("test")
+            .join("bad-multiple-errors.graphqxl");
+        let err = graphqxl_to_graphql(&args(vec![path.clone()], None)).unwrap_err();
+        let all = diagnostics(err);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].start.unwrap().line, 6);
+        assert_eq!(all[1].start.unwrap().line, 8);
+
+        let mut args = args(vec![path], None);
+        args.max_errors = Some(1);
+        let err = graphqxl_to_graphql(&args).unwrap_err();
+        assert!(err.to_string().ends_with("... and 1 more error(s)"));
+        assert!(!err.to_string().contains("field di does not exist"));
+        assert_eq!(diagnostics(err).len(), 1);
+    }
+
+    #[test]
+    fn test_reports_non_fatal_diagnostics_as_warnings() {
+        let path = Path::new("src")
+            .join("test")
+            .join("_undefined-directive.graphqxl");
+        let mut args = args(vec![path], None);
+        let compiled = graphqxl_to_graphql(&args).unwrap();
+        let warnings = compiled.warnings.diagnostics();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, Severity::Warning);
+        assert_eq!(warnings[0].code, "undefined-directive");
+        assert_eq!(warnings[0].start.unwrap().line, 2);
+        assert!(compiled
+            .warnings
+            .to_string()
+            .starts_with("warning[undefined-directive]: directive `@foo` is not defined\n"));
+
+        args.deny_warnings = true;
+        let err = graphqxl_to_graphql(&args).unwrap_err();
+        let denied = diagnostic(err);
+        assert_eq!(denied.severity, Severity::Error);
+        assert_eq!(denied.code, "undefined-directive");
+
+        args.allow = vec!["undefined-directive".to_string()];
+        let compiled = graphqxl_to_graphql(&args).unwrap();
+        assert!(compiled.warnings.is_empty());
+
+        args.allow = vec!["undefined-directives".to_string()];
+        let err = graphqxl_to_graphql(&args).unwrap_err();
+        assert_eq!(diagnostic(err).code, "config");
+    }
+
+    #[test]
+    fn test_output_needs_a_single_input() {
+        let test_dir = Path::new("src").join("test").join("_entrypoints");
+        let mut args = args(
+            vec![
+                test_dir.join("public.graphqxl"),
+                test_dir.join("internal.graphqxl"),
+            ],
+            None,
+        );
+        args.output = Some("out.graphql".to_string());
+        assert!(graphqxl_to_graphql(&args).is_err());
+    }
 }
@@ -1,12 +1,16 @@
-use anyhow::{anyhow, Result};
+use crate::diagnostic::DiagnosticError;
+use anyhow::Result;
 use std::error::Error;
 
+/// Turns an error raised at the stage identified by `code` into one that also carries
+/// its diagnostics, one for each of the errors it is made of.
 pub(crate) fn ok_or_anyhow_err<T>(
     result: std::result::Result<T, Box<dyn Error>>,
     msg: &str,
+    code: &str,
 ) -> Result<T> {
     match result {
         Ok(ok) => Ok(ok),
-        Err(err) => Err(anyhow!("{}:\n\n{}", msg, err)),
+        Err(err) => Err(DiagnosticError::from_errors(err, msg, code).into()),
     }
 }
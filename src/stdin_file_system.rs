@@ -0,0 +1,53 @@
+use crate::entrypoint::STDIO;
+use graphqxl_parser::VirtualFileSystem;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Name given to the input read from stdin, as if it was a file of the base directory.
+const STDIN_FILE_NAME: &str = "<stdin>";
+
+/// File system where the `-` path refers to the content read from stdin. That content
+/// is placed in a base directory, so that its relative imports are resolved from there.
+pub(crate) struct StdinFileSystem<'a> {
+    path: PathBuf,
+    content: String,
+    inner: &'a dyn VirtualFileSystem,
+}
+
+impl<'a> StdinFileSystem<'a> {
+    pub(crate) fn new(
+        content: String,
+        base_dir: &Path,
+        inner: &'a dyn VirtualFileSystem,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            path: inner.canonicalize(base_dir)?.join(STDIN_FILE_NAME),
+            content,
+            inner,
+        })
+    }
+}
+
+impl<'a> VirtualFileSystem for StdinFileSystem<'a> {
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+        if path == self.path {
+            return Ok(self.content.clone());
+        }
+        self.inner.read_to_string(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+        if path == Path::new(STDIO) || path == self.path {
+            return Ok(self.path.clone());
+        }
+        self.inner.canonicalize(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path == self.path || self.inner.exists(path)
+    }
+
+    fn list_files(&self, dir: &Path, recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        self.inner.list_files(dir, recursive)
+    }
+}
@@ -0,0 +1,223 @@
+use graphqxl_parser::{NativeFileSystem, VirtualFileSystem};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// File system that remembers every path the compiler looks at while following the
+/// imports, including the ones that did not exist, so that creating them also counts
+/// as a change.
+#[derive(Default)]
+struct RecordingFileSystem {
+    files: RefCell<BTreeSet<PathBuf>>,
+    dirs: RefCell<BTreeSet<(PathBuf, bool)>>,
+}
+
+impl VirtualFileSystem for RecordingFileSystem {
+    fn read_to_string(&self, path: &Path) -> Result<String, Box<dyn Error>> {
+        self.files.borrow_mut().insert(path.to_path_buf());
+        NativeFileSystem.read_to_string(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+        self.files.borrow_mut().insert(path.to_path_buf());
+        NativeFileSystem.canonicalize(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.borrow_mut().insert(path.to_path_buf());
+        NativeFileSystem.exists(path)
+    }
+
+    fn list_files(&self, dir: &Path, recursive: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        self.dirs
+            .borrow_mut()
+            .insert((dir.to_path_buf(), recursive));
+        NativeFileSystem.list_files(dir, recursive)
+    }
+}
+
+/// Last modification time and size of each watched file, `None` if it does not exist.
+type Snapshot = BTreeMap<PathBuf, Option<(SystemTime, u64)>>;
+
+impl RecordingFileSystem {
+    fn snapshot(&self) -> Snapshot {
+        let mut paths = self.files.borrow().clone();
+        for (dir, recursive) in self.dirs.borrow().iter() {
+            // a file added to a directory imported with a glob is part of the graph too
+            paths.extend(
+                NativeFileSystem
+                    .list_files(dir, *recursive)
+                    .unwrap_or_default(),
+            );
+        }
+        paths
+            .into_iter()
+            .map(|path| {
+                let metadata = fs::metadata(&path)
+                    .ok()
+                    .and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+                (path, metadata)
+            })
+            .collect()
+    }
+}
+
+/// Keeps track of the files involved in the last compilation, which are the ones of the
+/// import graph, in order to know when a new one is needed.
+#[derive(Default)]
+pub(crate) struct Watcher {
+    last: Option<(RecordingFileSystem, Snapshot)>,
+}
+
+impl Watcher {
+    /// Runs `compile` if this is the first call or if any of the files it used last
+    /// time changed, returning its result, or `None` if nothing changed. As the files
+    /// are recorded again on every compilation, new imports are followed as soon as
+    /// they are added.
+    pub(crate) fn poll<T>(
+        &mut self,
+        compile: impl FnOnce(&dyn VirtualFileSystem) -> T,
+    ) -> Option<T> {
+        if let Some((vfs, snapshot)) = &self.last {
+            if vfs.snapshot() == *snapshot {
+                return None;
+            }
+        }
+        let vfs = RecordingFileSystem::default();
+        let result = compile(&vfs);
+        let snapshot = vfs.snapshot();
+        self.last = Some((vfs, snapshot));
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir::TempDir;
+    use graphqxl_parser::{parse_spec_vfs_with_options, ParseSpecOptions};
+
+    fn compile(
+        entrypoint: &Path,
+    ) -> impl Fn(&dyn VirtualFileSystem) -> Result<Vec<String>, String> + '_ {
+        move |vfs| {
+            let spec = parse_spec_vfs_with_options(entrypoint, vfs, &ParseSpecOptions::default())
+                .map_err(|err| err.to_string())?;
+            let mut types: Vec<String> = spec.types.keys().cloned().collect();
+            types.sort();
+            Ok(types)
+        }
+    }
+
+    #[test]
+    fn test_recompiles_when_an_import_changes() {
+        let dir = TempDir::new("watch-import-changes");
+        let entrypoint = dir.join("main.graphqxl");
+        fs::write(
+            &entrypoint,
+            "import \"user\"\n\ntype Query { user: User }\n",
+        )
+        .unwrap();
+        fs::write(dir.join("user.graphqxl"), "type User { id: ID }\n").unwrap();
+        fs::write(
+            dir.join("unrelated.graphqxl"),
+            "type Unrelated { id: ID }\n",
+        )
+        .unwrap();
+
+        let mut watcher = Watcher::default();
+        let result = watcher.poll(compile(&entrypoint));
+        assert_eq!(
+            result,
+            Some(Ok(vec!["Query".to_string(), "User".to_string()]))
+        );
+        assert_eq!(watcher.poll(compile(&entrypoint)), None);
+
+        fs::write(
+            dir.join("unrelated.graphqxl"),
+            "type Unrelated { name: String }\n",
+        )
+        .unwrap();
+        assert_eq!(watcher.poll(compile(&entrypoint)), None);
+
+        fs::write(
+            dir.join("user.graphqxl"),
+            "type User { id: ID }\n\ntype Post { id: ID }\n",
+        )
+        .unwrap();
+        let result = watcher.poll(compile(&entrypoint));
+        assert_eq!(
+            result,
+            Some(Ok(vec![
+                "Post".to_string(),
+                "Query".to_string(),
+                "User".to_string()
+            ]))
+        );
+        assert_eq!(watcher.poll(compile(&entrypoint)), None);
+    }
+
+    #[test]
+    fn test_follows_new_imports() {
+        let dir = TempDir::new("watch-new-imports");
+        let entrypoint = dir.join("main.graphqxl");
+        fs::write(&entrypoint, "type Query { id: ID }\n").unwrap();
+
+        let mut watcher = Watcher::default();
+        assert!(watcher.poll(compile(&entrypoint)).is_some());
+
+        fs::write(dir.join("user.graphqxl"), "type User { id: ID }\n").unwrap();
+        assert_eq!(watcher.poll(compile(&entrypoint)), None);
+
+        fs::write(
+            &entrypoint,
+            "import \"user\"\n\ntype Query { user: User }\n",
+        )
+        .unwrap();
+        assert!(watcher.poll(compile(&entrypoint)).is_some());
+
+        fs::write(
+            dir.join("user.graphqxl"),
+            "type User { id: ID }\n\ntype Post { id: ID }\n",
+        )
+        .unwrap();
+        let result = watcher.poll(compile(&entrypoint));
+        assert_eq!(
+            result,
+            Some(Ok(vec![
+                "Post".to_string(),
+                "Query".to_string(),
+                "User".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_keeps_watching_after_errors() {
+        let dir = TempDir::new("watch-errors");
+        let entrypoint = dir.join("main.graphqxl");
+        fs::write(
+            &entrypoint,
+            "import \"user\"\n\ntype Query { user: User }\n",
+        )
+        .unwrap();
+
+        let mut watcher = Watcher::default();
+        assert!(matches!(watcher.poll(compile(&entrypoint)), Some(Err(_))));
+        assert_eq!(watcher.poll(compile(&entrypoint)), None);
+
+        // the missing import was recorded, so creating it is a change
+        fs::write(dir.join("user.graphqxl"), "type User {\n").unwrap();
+        assert!(matches!(watcher.poll(compile(&entrypoint)), Some(Err(_))));
+
+        fs::write(dir.join("user.graphqxl"), "type User { id: ID }\n").unwrap();
+        let result = watcher.poll(compile(&entrypoint));
+        assert_eq!(
+            result,
+            Some(Ok(vec!["Query".to_string(), "User".to_string()]))
+        );
+    }
+}